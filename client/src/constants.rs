@@ -1,5 +0,0 @@
-pub const JLP_MINT: &str = "27G8MtK7VtTcCHkpASjSDdkWWYfoqT6ggEuKidVJidD4";
-pub const USDC_MINT: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
-
-pub const ADMIN_CONFIG_SEED: &str = "admin-config";
-pub const ASSET_LOOKUP_TABLE_SEED: &str = "asset-lookup-table";
@@ -0,0 +1,244 @@
+use anchor_client::solana_sdk::{
+    commitment_config::CommitmentConfig, pubkey::Pubkey, signature::Signature,
+};
+use anchor_lang::event::EVENT_IX_TAG_LE;
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use anyhow::{anyhow, Result};
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::RpcTransactionConfig;
+use solana_transaction_status::option_serializer::OptionSerializer;
+use solana_transaction_status::{UiCompiledInstruction, UiInstruction, UiTransactionEncoding};
+use std::str::FromStr;
+use unitas_oracle_sdk::event::*;
+
+const EVENT_AUTHORITY_SEED: &[u8] = b"__event_authority";
+
+macro_rules! unitas_events {
+    ($($name:ident),* $(,)?) => {
+        // Events are only read through `Debug` outside the tests
+        #[allow(dead_code)]
+        #[derive(Debug)]
+        pub enum UnitasEvent {
+            $($name($name),)*
+        }
+
+        impl UnitasEvent {
+            /// Decodes `discriminator || borsh(event)`, or `None` for an unknown discriminator.
+            pub fn decode(data: &[u8]) -> Result<Option<Self>> {
+                $(
+                    if let Some(mut body) = data.strip_prefix($name::DISCRIMINATOR) {
+                        let event = $name::deserialize(&mut body)
+                            .map_err(|e| anyhow!("Failed to decode {}: {:?}", stringify!($name), e))?;
+                        return Ok(Some(Self::$name(event)));
+                    }
+                )*
+                Ok(None)
+            }
+        }
+    };
+}
+
+unitas_events!(
+    AdminConfigCreated,
+    OperatorAdded,
+    OperatorRemoved,
+    AdminTransferProposed,
+    AdminTransferAccepted,
+    AdminTransferCompleted,
+    PausedUpdated,
+    MaxAumAgeUpdated,
+    NavPegBandUpdated,
+    UsduPriceFeedCreated,
+    AssetLookupTableCreated,
+    AssetLookupTableClosed,
+    ConfigMigrated,
+    AssetLookupTableMigrated,
+    AssetPegBandSet,
+    AssetPricingModeSet,
+    AssetPricePathSet,
+    AssetStakePoolSet,
+    AccountAdded,
+    AccountRemoved,
+    AccountLabelSet,
+    AssetValuationRecorded,
+    StablecoinDepegged,
+    StablecoinRepegged,
+    ValuationRoundStarted,
+    ValuationRoundFinalized,
+    AumUsdUpdated,
+    CollateralRatioThresholdCrossed,
+    NavPegDeviation,
+    NavPegRestored,
+    LiabilitiesCommitted,
+    CustodianAdded,
+    CustodianRemoved,
+    CustodianBalanceAttested,
+    CustodianAttestationExpired,
+);
+
+/// Reads the program's events from the self-CPI inner instructions of a transaction.
+///
+/// An inner instruction is only accepted when it targets the program and is signed by its
+/// event authority PDA, which nothing but the program itself can sign for.
+pub fn fetch_events(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+    signature: &Signature,
+) -> Result<Vec<UnitasEvent>> {
+    let tx = rpc_client.get_transaction_with_config(
+        signature,
+        RpcTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            commitment: Some(CommitmentConfig::confirmed()),
+            max_supported_transaction_version: Some(0),
+        },
+    )?;
+    let meta = tx
+        .transaction
+        .meta
+        .ok_or_else(|| anyhow!("Transaction {} has no status meta", signature))?;
+    if let Some(err) = meta.err {
+        return Err(anyhow!("Transaction {} failed: {:?}", signature, err));
+    }
+    let versioned_tx = tx
+        .transaction
+        .transaction
+        .decode()
+        .ok_or_else(|| anyhow!("Failed to decode transaction {}", signature))?;
+
+    // Static keys first, then addresses loaded from lookup tables, as the runtime orders them
+    let mut account_keys = versioned_tx.message.static_account_keys().to_vec();
+    if let OptionSerializer::Some(loaded) = &meta.loaded_addresses {
+        for key in loaded.writable.iter().chain(loaded.readonly.iter()) {
+            account_keys.push(Pubkey::from_str(key)?);
+        }
+    }
+
+    let inner_instructions = match meta.inner_instructions {
+        OptionSerializer::Some(inner_instructions) => inner_instructions,
+        _ => return Ok(Vec::new()),
+    };
+    let instructions = inner_instructions
+        .iter()
+        .flat_map(|inner| inner.instructions.iter())
+        .filter_map(|instruction| match instruction {
+            UiInstruction::Compiled(instruction) => Some(instruction),
+            _ => None,
+        });
+    decode_events(&account_keys, program_id, instructions)
+}
+
+/// Decodes the events among a transaction's compiled inner instructions, whose account
+/// indexes point into `account_keys`. Unknown events are reported and skipped.
+pub fn decode_events<'a>(
+    account_keys: &[Pubkey],
+    program_id: &Pubkey,
+    instructions: impl IntoIterator<Item = &'a UiCompiledInstruction>,
+) -> Result<Vec<UnitasEvent>> {
+    let (event_authority, _) = Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], program_id);
+    let mut events = Vec::new();
+    for instruction in instructions {
+        if account_keys.get(instruction.program_id_index as usize) != Some(program_id) {
+            continue;
+        }
+        let signed_by_event_authority = instruction.accounts.len() == 1
+            && account_keys.get(instruction.accounts[0] as usize) == Some(&event_authority);
+        if !signed_by_event_authority {
+            continue;
+        }
+
+        let data = bs58::decode(&instruction.data).into_vec()?;
+        let Some(payload) = data.strip_prefix(EVENT_IX_TAG_LE) else {
+            continue;
+        };
+        match UnitasEvent::decode(payload)? {
+            Some(event) => events.push(event),
+            None => println!("Warning: skipping an unknown event"),
+        }
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Event;
+
+    const PROGRAM_ID: Pubkey = unitas_oracle_sdk::ID;
+
+    fn event_authority() -> Pubkey {
+        Pubkey::find_program_address(&[EVENT_AUTHORITY_SEED], &PROGRAM_ID).0
+    }
+
+    /// `emit_cpi!` data: the event instruction tag followed by the event's discriminator and
+    /// Borsh body.
+    fn emit_cpi_data(event: &impl Event) -> String {
+        let mut data = EVENT_IX_TAG_LE.to_vec();
+        data.extend_from_slice(&event.data());
+        bs58::encode(data).into_string()
+    }
+
+    fn instruction(program_id_index: u8, accounts: Vec<u8>, data: String) -> UiCompiledInstruction {
+        UiCompiledInstruction {
+            program_id_index,
+            accounts,
+            data,
+            stack_height: Some(2),
+        }
+    }
+
+    fn operator_added() -> OperatorAdded {
+        OperatorAdded {
+            operator: Pubkey::new_unique(),
+            user: Pubkey::new_unique(),
+        }
+    }
+
+    #[test]
+    fn decodes_event_signed_by_the_event_authority() {
+        let event = operator_added();
+        let account_keys = [Pubkey::new_unique(), PROGRAM_ID, event_authority()];
+        let instructions = [instruction(1, vec![2], emit_cpi_data(&event))];
+
+        let events = decode_events(&account_keys, &PROGRAM_ID, &instructions).unwrap();
+        assert_eq!(events.len(), 1);
+        let UnitasEvent::OperatorAdded(decoded) = &events[0] else {
+            panic!("unexpected event {:?}", events[0]);
+        };
+        assert_eq!(decoded.operator, event.operator);
+        assert_eq!(decoded.user, event.user);
+    }
+
+    #[test]
+    fn skips_event_not_signed_by_the_event_authority() {
+        let spoofer = Pubkey::new_unique();
+        let account_keys = [spoofer, PROGRAM_ID, event_authority()];
+        let data = emit_cpi_data(&operator_added());
+        let instructions = [
+            // Same payload, but the signer is any account
+            instruction(1, vec![0], data.clone()),
+            // Event authority passed alongside another account
+            instruction(1, vec![2, 0], data.clone()),
+            // Event authority signing for another program
+            instruction(0, vec![2], data),
+        ];
+
+        let events = decode_events(&account_keys, &PROGRAM_ID, &instructions).unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn skips_instructions_that_are_not_events() {
+        let account_keys = [PROGRAM_ID, event_authority()];
+        let untagged = bs58::encode(operator_added().data()).into_string();
+        let mut unknown = EVENT_IX_TAG_LE.to_vec();
+        unknown.extend_from_slice(&[0xff; 16]);
+        let instructions = [
+            instruction(0, vec![1], untagged),
+            instruction(0, vec![1], bs58::encode(unknown).into_string()),
+        ];
+
+        let events = decode_events(&account_keys, &PROGRAM_ID, &instructions).unwrap();
+        assert!(events.is_empty());
+    }
+}
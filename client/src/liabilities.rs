@@ -0,0 +1,260 @@
+use crate::merkle::{verify_sum_proof, MerkleSumTree, SumNode};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::path::Path;
+use std::str::FromStr;
+use unitas_oracle_sdk::UnitasConfig;
+
+/// Snapshot of USDU holder balances committed with `commit_liabilities`.
+#[derive(Deserialize, Debug)]
+pub struct LiabilitiesReport {
+    pub holders: Vec<HolderBalance>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct HolderBalance {
+    pub holder: String,
+    pub balance: u128,
+}
+
+impl HolderBalance {
+    /// Leaf preimage: `holder || balance (u128 LE)`, with the balance as the leaf sum.
+    pub fn leaf(&self) -> Result<SumNode> {
+        let holder = Pubkey::from_str(&self.holder)?;
+        Ok(SumNode::leaf(holder.as_ref(), self.balance))
+    }
+}
+
+pub fn load_report(path: &Path) -> Result<LiabilitiesReport> {
+    let file = std::fs::File::open(path)?;
+    serde_json::from_reader(file)
+        .map_err(|e| anyhow!("Failed to parse liabilities report: {:?}", e))
+}
+
+pub fn verify_liabilities(
+    unitas_config: &UnitasConfig,
+    usdu_total_supply: u128,
+    report_path: &Path,
+    holder: &str,
+) -> Result<()> {
+    let report = load_report(report_path)?;
+    verify_report(unitas_config, usdu_total_supply, &report, holder)
+}
+
+/// Checks `report` against the committed root and total, proves `holder`'s balance in it and
+/// checks the committed total against the USDU supply.
+fn verify_report(
+    unitas_config: &UnitasConfig,
+    usdu_total_supply: u128,
+    report: &LiabilitiesReport,
+    holder: &str,
+) -> Result<()> {
+    let leaves = report
+        .holders
+        .iter()
+        .map(HolderBalance::leaf)
+        .collect::<Result<Vec<_>>>()?;
+    let tree = MerkleSumTree::new(leaves.clone())
+        .ok_or_else(|| anyhow!("Liabilities report total overflows u128"))?;
+    let committed_root = SumNode {
+        hash: unitas_config.liabilities_root,
+        sum: unitas_config.total_liabilities,
+    };
+
+    println!("\n--- Proof of Liabilities ---");
+    println!(
+        "Committed at: {}",
+        unitas_config.liabilities_updated_timestamp
+    );
+    println!(
+        "On-chain root: {} (total {})",
+        hex::encode(committed_root.hash),
+        committed_root.sum
+    );
+    println!(
+        "Rebuilt root:  {} (total {})",
+        hex::encode(tree.root().hash),
+        tree.root().sum
+    );
+    if tree.root() != committed_root {
+        return Err(anyhow!(
+            "Liabilities report does not match the on-chain commitment"
+        ));
+    }
+
+    let index = report
+        .holders
+        .iter()
+        .position(|h| h.holder == holder)
+        .ok_or_else(|| anyhow!("Holder {} not found in the liabilities report", holder))?;
+    let proof = tree.proof(index);
+    if !verify_sum_proof(leaves[index], &proof, &committed_root) {
+        return Err(anyhow!("Inclusion proof failed for holder {}", holder));
+    }
+    println!(
+        "\nHolder {} with balance {} is included at index {}",
+        holder, report.holders[index].balance, index
+    );
+    for (depth, step) in proof.iter().enumerate() {
+        println!(
+            "  proof[{}]: {} sum {} ({})",
+            depth,
+            hex::encode(step.sibling.hash),
+            step.sibling.sum,
+            if step.sibling_is_left {
+                "left"
+            } else {
+                "right"
+            }
+        );
+    }
+
+    println!("\nUSDU total supply: {}", usdu_total_supply);
+    if committed_root.sum != usdu_total_supply {
+        return Err(anyhow!(
+            "Committed total liabilities {} do not match USDU supply {}",
+            committed_root.sum,
+            usdu_total_supply
+        ));
+    }
+    println!("Committed total liabilities match USDU supply");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::merkle::{SumNode, SumProofStep};
+
+    const HOLDERS: [&str; 3] = [
+        "4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi",
+        "8qbHbw2BbbTHBW1sbeqakYXVKRQM8Ne7pLK7m6CVfeR",
+        "CktRuQ2mttgRGkXJtyksdKHjUdc2C4TgDzyB98oEzy8",
+    ];
+
+    fn report(balances: &[u128]) -> LiabilitiesReport {
+        LiabilitiesReport {
+            holders: HOLDERS
+                .iter()
+                .zip(balances)
+                .map(|(holder, balance)| HolderBalance {
+                    holder: holder.to_string(),
+                    balance: *balance,
+                })
+                .collect(),
+        }
+    }
+
+    fn leaves(report: &LiabilitiesReport) -> Vec<SumNode> {
+        report
+            .holders
+            .iter()
+            .map(|holder| holder.leaf().unwrap())
+            .collect()
+    }
+
+    /// Config committing to `report` as `commit_liabilities` would.
+    fn committed(report: &LiabilitiesReport) -> UnitasConfig {
+        let root = MerkleSumTree::new(leaves(report)).unwrap().root();
+        UnitasConfig {
+            liabilities_root: root.hash,
+            total_liabilities: root.sum,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn proves_every_holder_against_the_committed_total() {
+        let report = report(&[700, 200, 100]);
+        let config = committed(&report);
+        assert_eq!(config.total_liabilities, 1_000);
+        for holder in HOLDERS {
+            verify_report(&config, 1_000, &report, holder).unwrap();
+        }
+        assert!(verify_report(&config, 1_000, &report, "unknown").is_err());
+    }
+
+    #[test]
+    fn sum_proofs_round_trip_for_any_leaf_count() {
+        for count in 1..=7u8 {
+            let leaves: Vec<SumNode> = (0..count)
+                .map(|i| SumNode::leaf(&[i], i as u128 * 10))
+                .collect();
+            let tree = MerkleSumTree::new(leaves.clone()).unwrap();
+            assert_eq!(tree.root().sum, leaves.iter().map(|leaf| leaf.sum).sum());
+            for (index, leaf) in leaves.iter().enumerate() {
+                assert!(verify_sum_proof(*leaf, &tree.proof(index), &tree.root()));
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_committed_total_that_differs_from_the_report() {
+        let report = report(&[700, 200, 100]);
+        let mut config = committed(&report);
+        config.total_liabilities += 1;
+        assert!(verify_report(&config, 1_001, &report, HOLDERS[0]).is_err());
+    }
+
+    #[test]
+    fn rejects_total_liabilities_that_differ_from_supply() {
+        let report = report(&[700, 200, 100]);
+        let config = committed(&report);
+        assert!(verify_report(&config, 999, &report, HOLDERS[0]).is_err());
+        assert!(verify_report(&config, 1_001, &report, HOLDERS[0]).is_err());
+    }
+
+    #[test]
+    fn rejects_negative_and_overflowing_balances() {
+        let parse = |balance: &str| {
+            serde_json::from_str::<LiabilitiesReport>(&format!(
+                r#"{{"holders": [{{"holder": "{}", "balance": {}}}]}}"#,
+                HOLDERS[0], balance
+            ))
+        };
+        assert_eq!(parse("5").unwrap().holders[0].balance, 5);
+        assert!(parse("-5").is_err());
+        assert!(parse("340282366920938463463374607431768211456").is_err());
+
+        // Balances that fit on their own but not summed
+        let report = report(&[u128::MAX, 1]);
+        assert!(MerkleSumTree::new(leaves(&report)).is_none());
+        assert!(verify_report(&UnitasConfig::default(), 0, &report, HOLDERS[0]).is_err());
+    }
+
+    #[test]
+    fn rejects_node_sums_that_do_not_add_up() {
+        let report = report(&[700, 200, 100]);
+        let leaves = leaves(&report);
+        let tree = MerkleSumTree::new(leaves.clone()).unwrap();
+        let root = tree.root();
+
+        // A sibling claiming less than it holds lowers the total, which the root hash catches
+        let mut proof = tree.proof(0);
+        proof[0].sibling.sum -= 1;
+        assert!(!verify_sum_proof(leaves[0], &proof, &root));
+        assert!(!verify_sum_proof(
+            leaves[0],
+            &proof,
+            &SumNode {
+                sum: root.sum - 1,
+                ..root
+            }
+        ));
+
+        // Nor can a leaf inflate its own balance
+        let inflated = SumNode::leaf(Pubkey::from_str(HOLDERS[0]).unwrap().as_ref(), 701);
+        assert!(!verify_sum_proof(inflated, &tree.proof(0), &root));
+
+        // A sibling whose sum would overflow the parent fails instead of wrapping
+        let overflowing = [SumProofStep {
+            sibling: SumNode {
+                sum: u128::MAX,
+                ..leaves[1]
+            },
+            sibling_is_left: false,
+        }];
+        assert!(!verify_sum_proof(leaves[0], &overflowing, &root));
+    }
+}
@@ -0,0 +1,306 @@
+use anchor_client::solana_sdk::{
+    address_lookup_table::{
+        instruction::{create_lookup_table, extend_lookup_table},
+        state::{AddressLookupTable, LOOKUP_TABLE_MAX_ADDRESSES},
+    },
+    commitment_config::CommitmentConfig,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Keypair,
+    signer::Signer,
+    transaction::Transaction,
+};
+use anchor_lang::Discriminator;
+use anyhow::{anyhow, Result};
+use solana_account_decoder::UiAccountEncoding;
+use solana_client::rpc_client::RpcClient;
+use solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
+use solana_client::rpc_filter::{Memcmp, RpcFilterType};
+use std::collections::HashSet;
+use unitas_oracle_sdk::{accounts, instructions, AssetLookupTable, UnitasConfig};
+
+/// Addresses appended per `extend_lookup_table`, keeping each transaction under the size limit.
+const EXTEND_CHUNK_SIZE: usize = 20;
+
+/// Fetches every `AssetLookupTable` registered under `config`, in no particular order.
+pub fn fetch_asset_lookup_tables(
+    rpc_client: &RpcClient,
+    config: &Pubkey,
+) -> Result<Vec<(Pubkey, AssetLookupTable)>> {
+    let filters = vec![
+        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            0,
+            AssetLookupTable::DISCRIMINATOR.to_vec(),
+        )),
+        RpcFilterType::Memcmp(Memcmp::new_raw_bytes(8, config.to_bytes().to_vec())),
+    ];
+    let program_accounts = rpc_client.get_program_accounts_with_config(
+        &unitas_oracle_sdk::ID,
+        RpcProgramAccountsConfig {
+            filters: Some(filters),
+            account_config: RpcAccountInfoConfig {
+                encoding: Some(UiAccountEncoding::Base64),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    )?;
+
+    let mut tables = Vec::with_capacity(program_accounts.len());
+    for (address, account) in program_accounts {
+        match accounts::decode_asset_lookup_table(&account.data) {
+            Ok(table) => tables.push((address, table)),
+            // Tables still on an older layout can't be valued until they are migrated
+            Err(e) => println!("Warning: skipping AssetLookupTable {}: {:?}", address, e),
+        }
+    }
+    Ok(tables)
+}
+
+/// Every non-signer account touched by a valuation round of `config`, deduplicated, in a
+/// stable order: the shared accounts first, then each asset's table, oracle and holding accounts.
+pub fn valuation_addresses(
+    config: &Pubkey,
+    unitas_config: &UnitasConfig,
+    tables: &[(Pubkey, AssetLookupTable)],
+) -> Result<Vec<Pubkey>> {
+    let mut valuation_ixs = vec![instructions::finalize_valuation(
+        *config,
+        unitas_config.usdu_config,
+        true,
+    )];
+    for (address, table) in tables {
+        let ix = instructions::accumulate_asset(unitas_config.fund_id, table)
+            .map_err(|e| anyhow!("Failed to build the valuation of {}: {:?}", address, e))?;
+        valuation_ixs.push(ix);
+    }
+
+    let mut seen = HashSet::new();
+    let mut addresses = Vec::new();
+    for ix in &valuation_ixs {
+        let keys = std::iter::once(ix.program_id).chain(
+            ix.accounts
+                .iter()
+                .filter(|meta| !meta.is_signer)
+                .map(|meta| meta.pubkey),
+        );
+        for key in keys {
+            if seen.insert(key) {
+                addresses.push(key);
+            }
+        }
+    }
+    Ok(addresses)
+}
+
+/// Creates an address lookup table holding every account a valuation of `config` needs, or
+/// appends to `lookup_table` the ones it is missing.
+///
+/// Lookup tables are append-only, so addresses of removed owners stay in the table; they are
+/// listed so the table can be recreated once it runs out of room.
+pub fn sync_lookup_table(
+    rpc_client: &RpcClient,
+    config: &Pubkey,
+    unitas_config: &UnitasConfig,
+    payer: &Keypair,
+    lookup_table: Option<Pubkey>,
+) -> Result<()> {
+    let tables = fetch_asset_lookup_tables(rpc_client, config)?;
+    println!("Found {} AssetLookupTable accounts", tables.len());
+    for (address, table) in &tables {
+        println!(
+            "  {} (mint {}, {} owners)",
+            address, table.asset_mint, table.token_account_owners_len
+        );
+    }
+    let wanted = valuation_addresses(config, unitas_config, &tables)?;
+
+    let (lookup_table, existing) = match lookup_table {
+        Some(lookup_table) => {
+            let account = rpc_client.get_account(&lookup_table)?;
+            let state = AddressLookupTable::deserialize(&account.data)
+                .map_err(|e| anyhow!("Failed to deserialize address lookup table: {:?}", e))?;
+            if state.meta.authority != Some(payer.pubkey()) {
+                return Err(anyhow!(
+                    "{} is frozen or not owned by {}",
+                    lookup_table,
+                    payer.pubkey()
+                ));
+            }
+            (lookup_table, state.addresses.to_vec())
+        }
+        None => {
+            // The recent slot must be in the SlotHashes sysvar, so take a finalized one
+            let recent_slot = rpc_client.get_slot_with_commitment(CommitmentConfig::finalized())?;
+            let (ix, lookup_table) =
+                create_lookup_table(payer.pubkey(), payer.pubkey(), recent_slot);
+            send(rpc_client, payer, ix)?;
+            println!("Created address lookup table {}", lookup_table);
+            (lookup_table, Vec::new())
+        }
+    };
+
+    let (missing, stale) = missing_and_stale(&existing, &wanted);
+
+    if existing.len() + missing.len() > LOOKUP_TABLE_MAX_ADDRESSES {
+        return Err(anyhow!(
+            "{} needs {} more addresses but only has room for {}; recreate it without {} stale addresses",
+            lookup_table,
+            missing.len(),
+            LOOKUP_TABLE_MAX_ADDRESSES - existing.len(),
+            stale.len()
+        ));
+    }
+
+    for chunk in missing.chunks(EXTEND_CHUNK_SIZE) {
+        let ix = extend_lookup_table(
+            lookup_table,
+            payer.pubkey(),
+            Some(payer.pubkey()),
+            chunk.to_vec(),
+        );
+        send(rpc_client, payer, ix)?;
+    }
+
+    println!("\nAddress lookup table: {}", lookup_table);
+    println!("Added {} addresses", missing.len());
+    println!("{} addresses in total", existing.len() + missing.len());
+    if !stale.is_empty() {
+        println!(
+            "{} addresses are no longer used by any valuation:",
+            stale.len()
+        );
+        for key in stale {
+            println!("  {}", key);
+        }
+    }
+    Ok(())
+}
+
+/// Addresses of `wanted` the lookup table lacks, and addresses it holds that are no longer
+/// wanted, both in their original order.
+fn missing_and_stale(existing: &[Pubkey], wanted: &[Pubkey]) -> (Vec<Pubkey>, Vec<Pubkey>) {
+    let existing_set: HashSet<Pubkey> = existing.iter().copied().collect();
+    let missing = wanted
+        .iter()
+        .filter(|key| !existing_set.contains(key))
+        .copied()
+        .collect();
+    let wanted_set: HashSet<Pubkey> = wanted.iter().copied().collect();
+    let stale = existing
+        .iter()
+        .filter(|key| !wanted_set.contains(key))
+        .copied()
+        .collect();
+    (missing, stale)
+}
+
+fn send(rpc_client: &RpcClient, payer: &Keypair, ix: Instruction) -> Result<()> {
+    let blockhash = rpc_client.get_latest_blockhash()?;
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[payer], blockhash);
+    let signature = rpc_client.send_and_confirm_transaction(&tx)?;
+    println!("Transaction: {}", signature);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use unitas_oracle_sdk::{pda, AssetKind};
+
+    const FUND_ID: u16 = 3;
+
+    fn unitas_config() -> UnitasConfig {
+        UnitasConfig {
+            fund_id: FUND_ID,
+            usdu_config: Pubkey::new_unique(),
+            ..Default::default()
+        }
+    }
+
+    fn table(owners: &[Pubkey]) -> AssetLookupTable {
+        let mut table: AssetLookupTable = bytemuck::Zeroable::zeroed();
+        table.config = pda::config_address(FUND_ID);
+        table.asset_mint = Pubkey::new_unique();
+        table.oracle_account = Pubkey::new_unique();
+        table.asset_kind = AssetKind::SplToken as u8;
+        table.token_account_owners[..owners.len()].copy_from_slice(owners);
+        table.token_account_owners_len = owners.len() as u32;
+        table
+    }
+
+    fn non_signer_keys(ix: &Instruction) -> Vec<Pubkey> {
+        std::iter::once(ix.program_id)
+            .chain(
+                ix.accounts
+                    .iter()
+                    .filter(|meta| !meta.is_signer)
+                    .map(|meta| meta.pubkey),
+            )
+            .collect()
+    }
+
+    #[test]
+    fn valuation_addresses_cover_every_builder_account_once() {
+        let config = pda::config_address(FUND_ID);
+        let unitas_config = unitas_config();
+        // A shared owner across tables must only be listed once
+        let shared_owner = Pubkey::new_unique();
+        let tables: Vec<(Pubkey, AssetLookupTable)> = [
+            table(&[shared_owner, Pubkey::new_unique()]),
+            table(&[shared_owner]),
+        ]
+        .into_iter()
+        .map(|table| {
+            let address = pda::asset_lookup_table_address(FUND_ID, &table.asset_mint);
+            (address, table)
+        })
+        .collect();
+
+        let addresses = valuation_addresses(&config, &unitas_config, &tables).unwrap();
+
+        let mut expected = non_signer_keys(&instructions::finalize_valuation(
+            config,
+            unitas_config.usdu_config,
+            true,
+        ));
+        for (_, table) in &tables {
+            expected.extend(non_signer_keys(
+                &instructions::accumulate_asset(FUND_ID, table).unwrap(),
+            ));
+        }
+        let mut seen = HashSet::new();
+        expected.retain(|key| seen.insert(*key));
+        assert_eq!(addresses, expected);
+
+        for (address, _) in &tables {
+            assert!(addresses.contains(address));
+        }
+        let unique: HashSet<Pubkey> = addresses.iter().copied().collect();
+        assert_eq!(unique.len(), addresses.len());
+    }
+
+    #[test]
+    fn splits_missing_addresses_from_stale_ones() {
+        let keys: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let existing = [keys[0], keys[1], keys[2]];
+        let wanted = [keys[3], keys[1], keys[0]];
+
+        let (missing, stale) = missing_and_stale(&existing, &wanted);
+        assert_eq!(missing, vec![keys[3]]);
+        assert_eq!(stale, vec![keys[2]]);
+    }
+
+    #[test]
+    fn extends_in_chunks_that_fit_a_transaction() {
+        let wanted: Vec<Pubkey> = (0..2 * EXTEND_CHUNK_SIZE + 5)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        let (missing, _) = missing_and_stale(&[], &wanted);
+
+        let chunks: Vec<&[Pubkey]> = missing.chunks(EXTEND_CHUNK_SIZE).collect();
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks.iter().all(|chunk| chunk.len() <= EXTEND_CHUNK_SIZE));
+        assert_eq!(chunks.concat(), wanted);
+    }
+}
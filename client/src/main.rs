@@ -1,65 +1,82 @@
-use crate::constants::{ADMIN_CONFIG_SEED, ASSET_LOOKUP_TABLE_SEED, JLP_MINT, USDC_MINT};
-use anchor_client::solana_sdk::{commitment_config::CommitmentConfig, pubkey::Pubkey};
+use anchor_client::solana_sdk::{
+    commitment_config::CommitmentConfig,
+    pubkey::Pubkey,
+    rent::Rent,
+    signature::{read_keypair_file, Signature},
+    sysvar,
+};
 use anchor_lang::AccountDeserialize;
-use anchor_spl::token::TokenAccount;
 use anyhow::{anyhow, Result};
-use borsh::BorshDeserialize;
-use clap::Parser;
-use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use clap::{Parser, Subcommand};
 use solana_client::rpc_client::RpcClient;
-use spl_associated_token_account::get_associated_token_address;
+use std::path::PathBuf;
 use std::str::FromStr;
+use unitas_oracle_sdk::{
+    accounts, compose_price, pda, usd_value, AccountLabel, AssetLookupTable, PriceUpdateV2,
+    StakePoolRate, UnitasConfig, COLLATERAL_RATIO_THRESHOLDS_BPS, MAX_ORACLE_PRICE_AGE,
+};
 
-mod constants;
+mod events;
+mod liabilities;
+mod lookup_table;
+mod merkle;
+mod reserves;
 
 const AUM_VALUE_SCALE_DECIMALS: u8 = 6;
-const PROGRAM_ID: &str = "UtyXF479UFC78u1ZV94JtXmnd7njb5FmSYZfFFdP1sT";
-
-fn account_deserialize<T: BorshDeserialize>(data: &[u8]) -> Result<T> {
-    if data.len() < 8 {
-        return Err(anyhow!("Account data too short"));
-    }
-    let mut account_data: &[u8] = &data[8..];
-    T::deserialize(&mut account_data).map_err(|e| anyhow!("Failed to deserialize account: {:?}", e))
-}
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
     #[arg(short, long, default_value = "https://api.mainnet-beta.solana.com")]
     url: String,
-}
 
-#[derive(BorshDeserialize, Debug)]
-pub struct AssetLookupTable {
-    pub asset_mint: Pubkey,
-    pub oracle_account: Pubkey,
-    pub token_account_owners: [Pubkey; 16],
-    pub token_account_owners_len: u32,
-    pub decimals: u8,
-    pub paddings: [u8; 3],
-}
+    /// Fund whose config to read; each fund has its own config PDA
+    #[arg(long, default_value_t = 0)]
+    fund_id: u16,
 
-#[derive(BorshDeserialize, Debug)]
-pub struct UnitasConfig {
-    pub admin: Pubkey,
-    pub pending_admin: Pubkey,
-    pub aum_usd: u128,
-    pub last_updated_timestamp: i64,
-    pub usdu_config: Pubkey,
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
-#[derive(BorshDeserialize, Debug)]
-pub struct UsduConfig {
-    pub admin: Pubkey,
-    pub pending_admin: Pubkey,
-    pub access_registry: Pubkey,
-    pub bump: u8,
-    pub is_initialized: bool,
-    pub usdu_token: Pubkey,
-    pub usdu_token_bump: u8,
-    pub is_usdu_token_initialized: bool,
-    pub total_supply: u128,
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Compute the fund AUM and USDU price (default)
+    Aum,
+    /// Rebuild the reserves Merkle tree from a JSON report and prove a holding against the on-chain root
+    VerifyReserves {
+        /// Path to the JSON reserves report
+        #[arg(long)]
+        report: PathBuf,
+        /// Account of the holding to prove
+        #[arg(long)]
+        account: String,
+        /// Mint of the holding, when the account appears more than once
+        #[arg(long)]
+        mint: Option<String>,
+    },
+    /// Prove a USDU holder balance against the on-chain Merkle-sum root and check total liabilities against supply
+    VerifyLiabilities {
+        /// Path to the JSON holder balances report
+        #[arg(long)]
+        report: PathBuf,
+        /// USDU holder to prove
+        #[arg(long)]
+        holder: String,
+    },
+    /// Create or extend an address lookup table with every account a valuation round needs
+    SyncLookupTable {
+        /// Keypair paying for, and holding authority over, the lookup table
+        #[arg(long)]
+        keypair: PathBuf,
+        /// Existing lookup table to extend; a new one is created when omitted
+        #[arg(long)]
+        lookup_table: Option<String>,
+    },
+    /// Decode the program events emitted in a transaction
+    Events {
+        /// Transaction signature
+        signature: String,
+    },
 }
 
 pub fn ten_pow(exponent: impl Into<u32>) -> u128 {
@@ -106,139 +123,338 @@ fn calculate_asset_value(
     rpc_client: &RpcClient,
     asset_lookup_table: &AssetLookupTable,
 ) -> Result<u128> {
-    let oracle_acc = rpc_client.get_account(&asset_lookup_table.oracle_account)?;
-    let price_account: PriceUpdateV2 = PriceUpdateV2::try_deserialize(&mut &oracle_acc.data[..])
-        .map_err(|e| anyhow!("Failed to deserialize price account: {:?}", e))?;
-
-    let price = price_account.price_message.price;
-    let price_value: u128 = price.unsigned_abs() as u128;
-    let price_decimals: u8 = price_account.price_message.exponent.unsigned_abs() as u8;
-    let token_decimals = asset_lookup_table.decimals;
-
     println!(
         "\n--- Calculating Value for Mint: {} ---",
         asset_lookup_table.asset_mint
     );
-    println!("Raw price: {}", price_value);
+
+    // Chain the oracles of the price path, e.g. asset/SOL then SOL/USD
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)?
+        .as_secs() as i64;
+    let pricing_mode = asset_lookup_table
+        .pricing_mode()
+        .map_err(|e| anyhow!("Unsupported asset lookup table: {:?}", e))?;
+    println!("Pricing mode: {:?}", pricing_mode);
+    let mut quotes = Vec::new();
+    let mut feed_ids = Vec::new();
+    for leg in asset_lookup_table.price_path() {
+        let oracle_acc = rpc_client.get_account(&leg.oracle)?;
+        // The program's mirror of the Pyth receiver layout, as `leg_price` expects
+        let price_account: PriceUpdateV2 =
+            PriceUpdateV2::try_deserialize(&mut &oracle_acc.data[..])
+                .map_err(|e| anyhow!("Failed to deserialize price account: {:?}", e))?;
+        let message = price_account.price_message;
+        println!(
+            "Price leg {}: {} ema {} expo {}{}, published at {}",
+            leg.oracle,
+            message.price,
+            message.ema_price,
+            message.exponent,
+            if leg.divide { " (divides)" } else { "" },
+            message.publish_time
+        );
+        if now.saturating_sub(message.publish_time) > MAX_ORACLE_PRICE_AGE {
+            println!(
+                "Warning: price leg {} is older than {}s and would be rejected on chain",
+                leg.oracle, MAX_ORACLE_PRICE_AGE
+            );
+        }
+        quotes.push((
+            pricing_mode.leg_price(&message, leg.divide),
+            message.exponent,
+            leg.divide,
+        ));
+        feed_ids.push(message.feed_id);
+    }
+    if asset_lookup_table.check_price_feed_ids(&feed_ids).is_err() {
+        println!("Warning: the oracles' feed ids don't match the price path and would be rejected on chain");
+    }
+    let (mut price, price_exponent) =
+        compose_price(quotes).map_err(|e| anyhow!("Failed to compose the price: {:?}", e))?;
+    if asset_lookup_table.stake_pool != Pubkey::default() {
+        // Liquid staking token: the oracle prices SOL, the stake pool converts it
+        let stake_pool_acc = rpc_client.get_account(&asset_lookup_table.stake_pool)?;
+        let rate = StakePoolRate::parse(&stake_pool_acc.data)
+            .map_err(|e| anyhow!("Failed to deserialize stake pool: {:?}", e))?;
+        println!(
+            "Stake pool {}: {} lamports for {} pool tokens (updated in epoch {})",
+            asset_lookup_table.stake_pool,
+            rate.total_lamports,
+            rate.pool_token_supply,
+            rate.last_update_epoch
+        );
+        let epoch = rpc_client.get_epoch_info()?.epoch;
+        price = rate
+            .pool_token_price(price, epoch)
+            .map_err(|e| anyhow!("Failed to price the pool token: {:?}", e))?;
+    }
+    if asset_lookup_table.peg_band_bps != 0 {
+        let (pegged_price, is_depegged) = asset_lookup_table
+            .peg_price(price, price_exponent)
+            .map_err(|e| anyhow!("Failed to apply the peg band: {:?}", e))?;
+        if is_depegged {
+            println!(
+                "Warning: price {} is outside the {} bps peg band, valuing at the oracle price",
+                price, asset_lookup_table.peg_band_bps
+            );
+        } else {
+            println!(
+                "Price {} is within the {} bps peg band, valuing at $1",
+                price, asset_lookup_table.peg_band_bps
+            );
+        }
+        price = pegged_price;
+    }
+    println!("Raw price: {} expo {}", price, price_exponent);
     println!(
         "Actual price: {}",
-        price_value as f64 / ten_pow(price_decimals) as f64
+        price as f64 * 10f64.powi(price_exponent)
     );
+    if asset_lookup_table.last_valued_at != 0 {
+        println!(
+            "Cached valuation at {} ({:?}): price {} expo {}, amount {}, USD value {}",
+            asset_lookup_table.last_valued_at,
+            asset_lookup_table
+                .last_valuation_source()
+                .map_err(|e| anyhow!("Unsupported asset lookup table: {:?}", e))?,
+            asset_lookup_table.last_price,
+            asset_lookup_table.last_price_exponent,
+            asset_lookup_table.last_total_amount,
+            asset_lookup_table.last_usd_value
+        );
+    }
+
+    let asset_kind = asset_lookup_table
+        .asset_kind()
+        .map_err(|e| anyhow!("Unsupported asset lookup table: {:?}", e))?;
+    println!("Asset kind: {:?}", asset_kind);
+    // Native SOL and stake balances are valued above their rent-exempt minimum
+    let rent: Rent = bincode::deserialize(&rpc_client.get_account(&sysvar::rent::ID)?.data)?;
 
     let owners_slice = &asset_lookup_table.token_account_owners
         [..asset_lookup_table.token_account_owners_len as usize];
 
-    let token_accounts: Vec<(Pubkey, TokenAccount)> = owners_slice
-        .iter()
-        .filter_map(|owner| {
-            let pubkey = get_associated_token_address(owner, &asset_lookup_table.asset_mint);
-            match rpc_client.get_account(&pubkey) {
-                Ok(account) => match TokenAccount::try_deserialize(&mut &account.data[..]) {
-                    Ok(token_account) => Some((pubkey, token_account)),
-                    Err(_) => {
-                        println!("Warning: Failed to deserialize token account {}", pubkey);
-                        None
-                    }
-                },
-                Err(_) => None, // Token account does not exist or RPC error
-            }
-        })
-        .collect();
-
-    let mut total_asset_value: u128 = 0;
-    for (ata_pubkey, token_account) in token_accounts.iter() {
-        let token_amount: u128 = token_account.amount.into();
-        println!(
-            "\nProcessing Owner: {}, ATA: {}",
-            token_account.owner, ata_pubkey
-        );
-        println!("Raw token amount: {}", token_amount);
-
-        let raw_value = price_value * token_amount;
-        let total_decimals = price_decimals + token_decimals;
-        let asset_value_usd = if total_decimals > AUM_VALUE_SCALE_DECIMALS {
-            let diff = total_decimals - AUM_VALUE_SCALE_DECIMALS;
-            raw_value / ten_pow(diff)
-        } else {
-            let diff = AUM_VALUE_SCALE_DECIMALS - total_decimals;
-            raw_value * ten_pow(diff)
+    // Valued like `accumulate_asset`: the holdings are summed first, then priced once
+    let mut total_amount: u64 = 0;
+    for owner in owners_slice {
+        let holding = asset_lookup_table
+            .holding_address(owner)
+            .map_err(|e| anyhow!("Failed to derive the holding of {}: {:?}", owner, e))?;
+        // The holding account does not exist or RPC error
+        let Ok(account) = rpc_client.get_account(&holding) else {
+            continue;
         };
-        println!("USD value (scaled): {}", asset_value_usd);
-        total_asset_value += asset_value_usd;
+        let holding_amount =
+            asset_kind.holding_amount(&account.owner, account.lamports, &account.data, &rent);
+        let Ok(amount) = holding_amount else {
+            println!("Warning: Failed to deserialize holding account {}", holding);
+            continue;
+        };
+        println!("\nProcessing Owner: {}, Holding: {}", owner, holding);
+        if let Some(account_label) =
+            fetch_account_label(rpc_client, &asset_lookup_table.config, owner)
+        {
+            println!(
+                "Label: {} ({:?})",
+                account_label.label(),
+                account_label.category
+            );
+        }
+        println!("Raw token amount: {}", amount);
+        total_amount = total_amount
+            .checked_add(amount)
+            .ok_or_else(|| anyhow!("Total amount of {} overflows", owner))?;
     }
 
+    let total_asset_value = usd_value(
+        total_amount,
+        asset_lookup_table.decimals,
+        price,
+        price_exponent,
+    )
+    .map_err(|e| anyhow!("Failed to value the asset: {:?}", e))?;
+    println!("Total amount: {}", total_amount);
     println!("Total USD value for this asset: {}", total_asset_value);
-    Ok(total_asset_value)
+    Ok(total_asset_value.into())
+}
+
+/// Label of a tracked owner, or `None` when it was never labelled.
+fn fetch_account_label(
+    rpc_client: &RpcClient,
+    config: &Pubkey,
+    owner: &Pubkey,
+) -> Option<AccountLabel> {
+    let account = rpc_client
+        .get_account(&pda::account_label_address(config, owner))
+        .ok()?;
+    accounts::decode_account_label(&account.data).ok()
+}
+
+fn fetch_unitas_config(rpc_client: &RpcClient, unitas_config_pda: &Pubkey) -> Result<UnitasConfig> {
+    println!("Derived UnitasConfig PDA: {}", unitas_config_pda);
+
+    let unitas_config_acc = rpc_client.get_account(unitas_config_pda)?;
+    accounts::decode_config(&unitas_config_acc.data)
+        .map_err(|e| anyhow!("Failed to deserialize UnitasConfig: {:?}", e))
 }
 
 fn main() -> Result<()> {
     let args = Args::parse();
     let rpc_client = RpcClient::new_with_commitment(args.url, CommitmentConfig::confirmed());
-    let program_id = Pubkey::from_str(PROGRAM_ID)?;
-
-    // 1. Derive the global UnitasConfig PDA
-    let (unitas_config_pda, _) =
-        Pubkey::find_program_address(&[ADMIN_CONFIG_SEED.as_bytes()], &program_id);
-    println!("Derived UnitasConfig PDA: {}", unitas_config_pda);
+    let program_id = unitas_oracle_sdk::ID;
+    let config = pda::config_address(args.fund_id);
+
+    match args.command.unwrap_or(Command::Aum) {
+        Command::Aum => compute_aum(&rpc_client, &config),
+        Command::VerifyReserves {
+            report,
+            account,
+            mint,
+        } => {
+            let unitas_config = fetch_unitas_config(&rpc_client, &config)?;
+            reserves::verify_reserves(&unitas_config, &report, &account, mint.as_deref())
+        }
+        Command::VerifyLiabilities { report, holder } => {
+            let unitas_config = fetch_unitas_config(&rpc_client, &config)?;
+            let usdu_config_acc = rpc_client.get_account(&unitas_config.usdu_config)?;
+            let usdu_config = accounts::decode_usdu_config(&usdu_config_acc.data)
+                .map_err(|e| anyhow!("Failed to deserialize USDU config: {:?}", e))?;
+            liabilities::verify_liabilities(
+                &unitas_config,
+                usdu_config.total_supply,
+                &report,
+                &holder,
+            )
+        }
+        Command::SyncLookupTable {
+            keypair,
+            lookup_table,
+        } => {
+            let payer = read_keypair_file(&keypair)
+                .map_err(|e| anyhow!("Failed to read keypair {}: {}", keypair.display(), e))?;
+            let address = lookup_table.as_deref().map(Pubkey::from_str).transpose()?;
+            let unitas_config = fetch_unitas_config(&rpc_client, &config)?;
+            lookup_table::sync_lookup_table(&rpc_client, &config, &unitas_config, &payer, address)
+        }
+        Command::Events { signature } => {
+            let signature = Signature::from_str(&signature)?;
+            for event in events::fetch_events(&rpc_client, &program_id, &signature)? {
+                println!("{:#?}", event);
+            }
+            Ok(())
+        }
+    }
+}
 
-    let unitas_config_acc = rpc_client.get_account(&unitas_config_pda)?;
-    let unitas_config = account_deserialize::<UnitasConfig>(&unitas_config_acc.data)?;
+fn compute_aum(rpc_client: &RpcClient, config: &Pubkey) -> Result<()> {
+    // 1. Fetch the fund's UnitasConfig PDA
+    let unitas_config = fetch_unitas_config(rpc_client, config)?;
 
     println!(
         "Unitas Config Last Updated Timestamp: {}",
         unitas_config.last_updated_timestamp
     );
-
-    // 2. Initialize total_value with the base AUM from the config
-    let mut total_value: u128 = unitas_config.aum_usd;
-    println!("Initial AUM from Config: {}", total_value);
-
-    // 3. Iterate through the list of tracked asset mints
-    let tracked_asset_mints = vec![Pubkey::from_str(JLP_MINT)?, Pubkey::from_str(USDC_MINT)?];
-
-    for asset_mint in tracked_asset_mints {
-        // 4. Derive and validate the AssetLookupTable PDA for each mint
-        let (asset_lookup_table_pda, _) = Pubkey::find_program_address(
-            &[ASSET_LOOKUP_TABLE_SEED.as_bytes(), asset_mint.as_ref()],
-            &program_id,
-        );
-
+    println!(
+        "Unitas Config Last Updated Slot: {}",
+        unitas_config.last_updated_slot
+    );
+    println!(
+        "Unitas Config AUM Sequence: {} (as of {})",
+        unitas_config.aum_sequence, unitas_config.aum_as_of
+    );
+    println!(
+        "Published NAV: {} (paused: {})",
+        unitas_config.nav as f64 / ten_pow(AUM_VALUE_SCALE_DECIMALS) as f64,
+        unitas_config.is_paused
+    );
+    println!(
+        "Published collateral ratio: {:.2}% (surplus {})",
+        unitas_config.collateral_ratio_bps as f64 / 100.0,
+        unitas_config.collateral_surplus_usd as f64 / ten_pow(AUM_VALUE_SCALE_DECIMALS) as f64
+    );
+    for (i, threshold_bps) in COLLATERAL_RATIO_THRESHOLDS_BPS.iter().enumerate() {
+        if unitas_config.collateral_breaches & (1 << i) != 0 {
+            println!(
+                "Warning: collateral ratio is below {:.2}%",
+                *threshold_bps as f64 / 100.0
+            );
+        }
+    }
+    println!(
+        "Published NAV deviation from 1.0: {} bps (peg band: {} bps)",
+        unitas_config.nav_deviation_bps, unitas_config.nav_peg_band_bps
+    );
+    if unitas_config.nav_off_peg_since != 0 {
         println!(
-            "\nDerived AssetLookupTable PDA for mint {}: {}",
-            asset_mint, asset_lookup_table_pda
+            "Warning: NAV has been outside its peg band since {}",
+            unitas_config.nav_off_peg_since
         );
+    }
 
-        let lookup_table_acc_result = rpc_client.get_account(&asset_lookup_table_pda);
-        if lookup_table_acc_result.is_err() {
+    // The USDU price feed uses the Pyth `PriceUpdateV2` layout, decoded with the same mirror
+    let price_feed_pda = pda::price_feed_address(config);
+    match rpc_client.get_account(&price_feed_pda) {
+        Ok(price_feed_acc) => {
+            let price_feed = PriceUpdateV2::try_deserialize(&mut &price_feed_acc.data[..])
+                .map_err(|e| anyhow!("Failed to deserialize USDU price feed: {:?}", e))?;
             println!(
-                "Warning: AssetLookupTable not found for mint {}. Skipping.",
-                asset_mint
+                "USDU price feed {}: price {} ema {} expo {} published at {}",
+                price_feed_pda,
+                price_feed.price_message.price,
+                price_feed.price_message.ema_price,
+                price_feed.price_message.exponent,
+                price_feed.price_message.publish_time
             );
-            continue;
         }
+        Err(_) => println!("Warning: USDU price feed {} not found", price_feed_pda),
+    }
 
-        let lookup_table_acc = lookup_table_acc_result.unwrap();
-        let asset_lookup_table = account_deserialize::<AssetLookupTable>(&lookup_table_acc.data)?;
+    // 2. Total AUM as the program computes it: the operator-reported value, which excludes the
+    // registered asset tables, the verified custodian balance statements and the on-chain
+    // valuation of those tables
+    let total_value = unitas_config
+        .total_aum_usd()
+        .map_err(|e| anyhow!("Failed to total the AUM: {:?}", e))?;
+    println!("Reported AUM from Config: {}", unitas_config.aum_usd);
+    println!(
+        "Attested custodian AUM from Config: {}",
+        unitas_config.attested_aum_usd
+    );
+    println!(
+        "On-chain AUM from last valuation round: {} (as of {}, {} assets)",
+        unitas_config.onchain_aum_usd,
+        unitas_config.onchain_aum_timestamp,
+        unitas_config.asset_count
+    );
 
-        // 5. Perform the crucial validation
-        if asset_lookup_table.asset_mint != asset_mint {
-            return Err(anyhow!(
-                "Validation failed! PDA {} does not contain the expected mint {}. Found {} instead.",
-                asset_lookup_table_pda,
-                asset_mint,
-                asset_lookup_table.asset_mint
-            ));
-        }
-        println!("PDA validation successful for mint {}", asset_mint);
+    // The asset tables are valued live below to cross-check the last valuation round
+    let mut live_onchain_value: u128 = 0;
+
+    // 3. Value every asset table registered under the config
+    let tables = lookup_table::fetch_asset_lookup_tables(rpc_client, config)?;
+    println!("\nFound {} AssetLookupTable accounts", tables.len());
+    for (address, asset_lookup_table) in tables {
+        println!(
+            "\nAssetLookupTable {} for mint {}",
+            address, asset_lookup_table.asset_mint
+        );
 
-        // 6. Calculate the value for this asset and add it to the total
-        let asset_value = calculate_asset_value(&rpc_client, &asset_lookup_table)?;
-        total_value += asset_value;
+        // 4. Calculate the live value of this asset
+        let asset_value = calculate_asset_value(rpc_client, &asset_lookup_table)?;
+        live_onchain_value += asset_value;
     }
 
-    // 7. Fetch USDU total supply for price calculation
+    println!("\nLive value of the asset tables: {}", live_onchain_value);
+    println!(
+        "Drift since the last valuation round: {}",
+        live_onchain_value as i128 - unitas_config.onchain_aum_usd as i128
+    );
+
+    // 5. Fetch USDU total supply for price calculation
     let usdu_config_acc = rpc_client.get_account(&unitas_config.usdu_config)?;
-    let usdu_config = account_deserialize::<UsduConfig>(&usdu_config_acc.data)?;
+    let usdu_config = accounts::decode_usdu_config(&usdu_config_acc.data)
+        .map_err(|e| anyhow!("Failed to deserialize USDU config: {:?}", e))?;
 
     println!("\n--- Final AUM Calculation ---");
     println!("Total AUM value: {}", total_value);
@@ -251,6 +467,15 @@ fn main() -> Result<()> {
         "USDU price: {}",
         total_value as f64 / usdu_config.total_supply as f64
     );
+    println!(
+        "Collateral ratio: {:.2}%",
+        total_value as f64 * 100.0 / usdu_config.total_supply as f64
+    );
+    println!(
+        "Collateral surplus (negative in deficit): {}",
+        (total_value as f64 - usdu_config.total_supply as f64)
+            / ten_pow(AUM_VALUE_SCALE_DECIMALS) as f64
+    );
 
     Ok(())
 }
@@ -0,0 +1,240 @@
+use solana_sdk::hash::hashv;
+
+// Domain tags keep a leaf from ever being reinterpreted as an inner node.
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+pub type Hash = [u8; 32];
+
+pub fn hash_leaf(data: &[u8]) -> Hash {
+    hashv(&[LEAF_PREFIX, data]).to_bytes()
+}
+
+pub fn hash_node(left: &Hash, right: &Hash) -> Hash {
+    hashv(&[NODE_PREFIX, left, right]).to_bytes()
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ProofStep {
+    pub sibling: Hash,
+    pub sibling_is_left: bool,
+}
+
+/// Positional binary SHA-256 Merkle tree; an odd node at the end of a level is
+/// promoted to the next level unchanged. The root of an empty tree is all zeroes.
+pub struct MerkleTree {
+    levels: Vec<Vec<Hash>>,
+}
+
+impl MerkleTree {
+    pub fn new(leaves: Vec<Hash>) -> Self {
+        let mut levels = vec![leaves];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => hash_node(left, right),
+                    [single] => *single,
+                    _ => unreachable!(),
+                })
+                .collect();
+            levels.push(next);
+        }
+        Self { levels }
+    }
+
+    pub fn root(&self) -> Hash {
+        self.levels
+            .last()
+            .and_then(|level| level.first())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn proof(&self, mut index: usize) -> Vec<ProofStep> {
+        let mut proof = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling_index = index ^ 1;
+            if let Some(sibling) = level.get(sibling_index) {
+                proof.push(ProofStep {
+                    sibling: *sibling,
+                    sibling_is_left: sibling_index < index,
+                });
+            }
+            index /= 2;
+        }
+        proof
+    }
+}
+
+pub fn verify_proof(leaf: Hash, proof: &[ProofStep], root: &Hash) -> bool {
+    let computed = proof.iter().fold(leaf, |acc, step| {
+        if step.sibling_is_left {
+            hash_node(&step.sibling, &acc)
+        } else {
+            hash_node(&acc, &step.sibling)
+        }
+    });
+    computed == *root
+}
+
+/// Node of a Merkle-sum tree: every node commits to the sum of the leaves below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SumNode {
+    pub hash: Hash,
+    pub sum: u128,
+}
+
+impl SumNode {
+    pub fn leaf(data: &[u8], value: u128) -> Self {
+        Self {
+            hash: hashv(&[LEAF_PREFIX, data, &value.to_le_bytes()]).to_bytes(),
+            sum: value,
+        }
+    }
+
+    pub fn parent(left: &SumNode, right: &SumNode) -> Option<Self> {
+        Some(Self {
+            hash: hashv(&[
+                NODE_PREFIX,
+                &left.hash,
+                &left.sum.to_le_bytes(),
+                &right.hash,
+                &right.sum.to_le_bytes(),
+            ])
+            .to_bytes(),
+            sum: left.sum.checked_add(right.sum)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SumProofStep {
+    pub sibling: SumNode,
+    pub sibling_is_left: bool,
+}
+
+/// Merkle-sum counterpart of [`MerkleTree`], with the same shape rules.
+pub struct MerkleSumTree {
+    levels: Vec<Vec<SumNode>>,
+}
+
+impl MerkleSumTree {
+    pub fn new(leaves: Vec<SumNode>) -> Option<Self> {
+        let mut levels = vec![leaves];
+        while levels.last().unwrap().len() > 1 {
+            let next = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => SumNode::parent(left, right),
+                    [single] => Some(*single),
+                    _ => unreachable!(),
+                })
+                .collect::<Option<Vec<_>>>()?;
+            levels.push(next);
+        }
+        Some(Self { levels })
+    }
+
+    pub fn root(&self) -> SumNode {
+        self.levels
+            .last()
+            .and_then(|level| level.first())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn proof(&self, mut index: usize) -> Vec<SumProofStep> {
+        let mut proof = Vec::new();
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling_index = index ^ 1;
+            if let Some(sibling) = level.get(sibling_index) {
+                proof.push(SumProofStep {
+                    sibling: *sibling,
+                    sibling_is_left: sibling_index < index,
+                });
+            }
+            index /= 2;
+        }
+        proof
+    }
+}
+
+pub fn verify_sum_proof(leaf: SumNode, proof: &[SumProofStep], root: &SumNode) -> bool {
+    let computed = proof.iter().try_fold(leaf, |acc, step| {
+        if step.sibling_is_left {
+            SumNode::parent(&step.sibling, &acc)
+        } else {
+            SumNode::parent(&acc, &step.sibling)
+        }
+    });
+    computed.as_ref() == Some(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaves(count: u8) -> Vec<Hash> {
+        (0..count).map(|i| hash_leaf(&[i])).collect()
+    }
+
+    #[test]
+    fn every_leaf_proves_against_the_root() {
+        for count in [1, 2, 3, 4, 5, 7, 8] {
+            let leaves = leaves(count);
+            let tree = MerkleTree::new(leaves.clone());
+            for (index, leaf) in leaves.iter().enumerate() {
+                let proof = tree.proof(index);
+                assert!(
+                    verify_proof(*leaf, &proof, &tree.root()),
+                    "leaf {index} of {count}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn single_leaf_is_its_own_root() {
+        let leaf = hash_leaf(b"only");
+        let tree = MerkleTree::new(vec![leaf]);
+        assert_eq!(tree.root(), leaf);
+        assert!(tree.proof(0).is_empty());
+        assert_eq!(MerkleTree::new(Vec::new()).root(), [0; 32]);
+    }
+
+    #[test]
+    fn odd_node_is_promoted_unchanged() {
+        let leaves = leaves(3);
+        let tree = MerkleTree::new(leaves.clone());
+        assert_eq!(
+            tree.root(),
+            hash_node(&hash_node(&leaves[0], &leaves[1]), &leaves[2])
+        );
+    }
+
+    #[test]
+    fn rejects_tampered_leaf() {
+        let tree = MerkleTree::new(leaves(5));
+        let proof = tree.proof(2);
+        assert!(!verify_proof(hash_leaf(&[9]), &proof, &tree.root()));
+    }
+
+    #[test]
+    fn rejects_tampered_sibling() {
+        let leaves = leaves(4);
+        let tree = MerkleTree::new(leaves.clone());
+
+        let mut proof = tree.proof(1);
+        proof[0].sibling[0] ^= 1;
+        assert!(!verify_proof(leaves[1], &proof, &tree.root()));
+
+        let mut proof = tree.proof(1);
+        proof[1].sibling_is_left = !proof[1].sibling_is_left;
+        assert!(!verify_proof(leaves[1], &proof, &tree.root()));
+    }
+}
@@ -0,0 +1,144 @@
+use crate::merkle::{hash_leaf, verify_proof, Hash, MerkleTree};
+use anchor_client::solana_sdk::pubkey::Pubkey;
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use std::path::Path;
+use std::str::FromStr;
+use unitas_oracle_sdk::UnitasConfig;
+
+/// Per-account balances behind an `update_aum_usd` call, as published at `reserves_uri`.
+#[derive(Deserialize, Debug)]
+pub struct ReservesReport {
+    pub holdings: Vec<Holding>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Holding {
+    pub account: String,
+    pub mint: String,
+    pub amount: u64,
+    pub usd_value: u128,
+}
+
+impl Holding {
+    /// Leaf preimage: `account || mint || amount (u64 LE) || usd_value (u128 LE)`.
+    pub fn leaf(&self) -> Result<Hash> {
+        let account = Pubkey::from_str(&self.account)?;
+        let mint = Pubkey::from_str(&self.mint)?;
+        let mut data = Vec::with_capacity(32 + 32 + 8 + 16);
+        data.extend_from_slice(account.as_ref());
+        data.extend_from_slice(mint.as_ref());
+        data.extend_from_slice(&self.amount.to_le_bytes());
+        data.extend_from_slice(&self.usd_value.to_le_bytes());
+        Ok(hash_leaf(&data))
+    }
+}
+
+pub fn load_report(path: &Path) -> Result<ReservesReport> {
+    let file = std::fs::File::open(path)?;
+    serde_json::from_reader(file).map_err(|e| anyhow!("Failed to parse reserves report: {:?}", e))
+}
+
+pub fn verify_reserves(
+    unitas_config: &UnitasConfig,
+    report_path: &Path,
+    account: &str,
+    mint: Option<&str>,
+) -> Result<()> {
+    let report = load_report(report_path)?;
+    let leaves = report
+        .holdings
+        .iter()
+        .map(Holding::leaf)
+        .collect::<Result<Vec<_>>>()?;
+    let tree = MerkleTree::new(leaves.clone());
+
+    println!("\n--- Proof of Reserves ---");
+    println!("Report URI: {}", unitas_config.reserves_uri.as_str());
+    println!(
+        "On-chain root: {}",
+        hex::encode(unitas_config.reserves_root)
+    );
+    println!("Rebuilt root:  {}", hex::encode(tree.root()));
+    if tree.root() != unitas_config.reserves_root {
+        return Err(anyhow!(
+            "Reserves report does not match the on-chain commitment"
+        ));
+    }
+
+    let report_total: u128 = report.holdings.iter().map(|h| h.usd_value).sum();
+    println!("Report total USD value: {}", report_total);
+    println!("On-chain reported AUM: {}", unitas_config.aum_usd);
+
+    let index = report
+        .holdings
+        .iter()
+        .position(|h| h.account == account && mint.is_none_or(|mint| h.mint == mint))
+        .ok_or_else(|| anyhow!("Holding {} not found in the reserves report", account))?;
+    let holding = &report.holdings[index];
+    let proof = tree.proof(index);
+    if !verify_proof(leaves[index], &proof, &unitas_config.reserves_root) {
+        return Err(anyhow!("Inclusion proof failed for holding {}", account));
+    }
+
+    println!(
+        "\nHolding {} (mint {}, amount {}, USD value {}) is included at index {}",
+        holding.account, holding.mint, holding.amount, holding.usd_value, index
+    );
+    for (depth, step) in proof.iter().enumerate() {
+        println!(
+            "  proof[{}]: {} ({})",
+            depth,
+            hex::encode(step.sibling),
+            if step.sibling_is_left {
+                "left"
+            } else {
+                "right"
+            }
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn holding(account: &str, amount: u64) -> Holding {
+        Holding {
+            account: account.to_string(),
+            // [2; 32]
+            mint: "8qbHbw2BbbTHBW1sbeqakYXVKRQM8Ne7pLK7m6CVfeR".to_string(),
+            amount,
+            usd_value: amount as u128,
+        }
+    }
+
+    #[test]
+    fn leaf_encoding_matches_a_fixed_root() {
+        // Accounts [1; 32] and [3; 32]; the hashes were computed independently with SHA-256
+        // over `0x00 || account || mint || amount || usd_value` and `0x01 || left || right`
+        let holdings = [
+            holding("4vJ9JU1bJJE96FWSJKvHsmmFADCg4gpZQff4P3bkLKi", 1_500_000_000),
+            holding("CktRuQ2mttgRGkXJtyksdKHjUdc2C4TgDzyB98oEzy8", 250_000),
+        ];
+        let leaves = holdings
+            .iter()
+            .map(Holding::leaf)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(
+            hex::encode(leaves[0]),
+            "b537621a22075e5a1b87bd92d6692cbeb745ed94f696fc5d3755662f210c2da7"
+        );
+        assert_eq!(
+            hex::encode(MerkleTree::new(leaves).root()),
+            "dc63782ec226df977abdedbf07f84851faca6d3d5ed2ae69f4b337a300eb65fb"
+        );
+    }
+
+    #[test]
+    fn rejects_holding_with_invalid_account() {
+        assert!(holding("not a pubkey", 1).leaf().is_err());
+    }
+}
@@ -0,0 +1,383 @@
+//! Reads the USDU NAV published by the Unitas oracle from another on-chain program.
+//!
+//! The crate depends on neither Anchor nor the oracle program: the config account is decoded
+//! with plain offset reads, so pulling it in costs little compute and few dependencies.
+
+use solana_account_info::AccountInfo;
+use solana_clock::Clock;
+use solana_program_error::ProgramError;
+use solana_pubkey::{pubkey, Pubkey};
+
+pub const PROGRAM_ID: Pubkey = pubkey!("UtyXF479UFC78u1ZV94JtXmnd7njb5FmSYZfFFdP1sT");
+
+/// Decimals of `Nav::nav`; a NAV of 1.0 is `10^NAV_DECIMALS`.
+pub const NAV_DECIMALS: u8 = 6;
+
+const ADMIN_CONFIG_SEED: &[u8] = b"admin-config";
+const MAX_RESERVES_URI_LEN: usize = 200;
+// sha256("account:UnitasConfig")[..8]
+const CONFIG_DISCRIMINATOR: [u8; 8] = [104, 254, 55, 214, 105, 190, 104, 211];
+const CONFIG_VERSION: u8 = 1;
+
+/// NAV read from a fund's config account.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Nav {
+    pub config: Pubkey,
+    pub fund_id: u16,
+    pub nav: u64,
+    /// Total AUM behind the NAV, scaled to 6 decimals
+    pub aum_usd: u128,
+    pub usdu_supply: u128,
+    /// When the oldest input of the NAV was observed: the reported AUM and, while asset tables
+    /// are registered, the last on-chain valuation round
+    pub timestamp: i64,
+    pub slot: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NavError {
+    InvalidOwner,
+    InvalidDiscriminator,
+    UnsupportedVersion,
+    InvalidData,
+    InvalidAddress,
+    Paused,
+    Unavailable,
+    Stale,
+}
+
+impl NavError {
+    /// Offset of the `ProgramError::Custom` codes, clear of Anchor's error ranges.
+    pub const CODE_OFFSET: u32 = 0x5500;
+}
+
+impl From<NavError> for ProgramError {
+    fn from(error: NavError) -> Self {
+        ProgramError::Custom(NavError::CODE_OFFSET + error as u32)
+    }
+}
+
+/// Loads the NAV of fund `fund_id` from `config`, failing unless the account is owned by the
+/// oracle program and sits at that fund's config address, the oracle is not paused, and the
+/// NAV's inputs are at most `max_age` seconds old.
+///
+/// The address is derived with the bump stored in the config, so the check costs a single
+/// `create_program_address` rather than a `find_program_address` search.
+pub fn load_nav(
+    config: &AccountInfo,
+    fund_id: u16,
+    max_age: i64,
+    clock: &Clock,
+) -> Result<Nav, NavError> {
+    if *config.owner != PROGRAM_ID {
+        return Err(NavError::InvalidOwner);
+    }
+    let data = config
+        .try_borrow_data()
+        .map_err(|_| NavError::InvalidData)?;
+    let fields = ConfigFields::parse(&data)?;
+    if *config.key != config_address(fund_id, fields.bump)? {
+        return Err(NavError::InvalidAddress);
+    }
+
+    if fields.is_paused {
+        return Err(NavError::Paused);
+    }
+    if fields.last_updated_timestamp == 0 || fields.usdu_supply == 0 {
+        return Err(NavError::Unavailable);
+    }
+    let as_of = fields.nav_as_of();
+    if clock.unix_timestamp.saturating_sub(as_of) > max_age {
+        return Err(NavError::Stale);
+    }
+
+    Ok(Nav {
+        config: *config.key,
+        fund_id: fields.fund_id,
+        nav: fields.nav,
+        aum_usd: fields
+            .aum_usd
+            .checked_add(fields.attested_aum_usd)
+            .and_then(|aum| aum.checked_add(fields.onchain_aum_usd))
+            .ok_or(NavError::InvalidData)?,
+        usdu_supply: fields.usdu_supply,
+        timestamp: as_of,
+        slot: fields.last_updated_slot,
+    })
+}
+
+/// Address of the config of `fund_id`, mirroring the program's `fund_seed`: fund 0 keeps the
+/// address it had before funds were introduced.
+fn config_address(fund_id: u16, bump: u8) -> Result<Pubkey, NavError> {
+    let fund_seed = fund_id.to_le_bytes();
+    let fund_seed: &[u8] = if fund_id == 0 { &[] } else { &fund_seed };
+    Pubkey::create_program_address(&[ADMIN_CONFIG_SEED, fund_seed, &[bump]], &PROGRAM_ID)
+        .map_err(|_| NavError::InvalidAddress)
+}
+
+/// The `UnitasConfig` fields the NAV depends on.
+struct ConfigFields {
+    fund_id: u16,
+    aum_usd: u128,
+    last_updated_timestamp: i64,
+    last_updated_slot: u64,
+    aum_as_of: i64,
+    attested_aum_usd: u128,
+    usdu_supply: u128,
+    nav: u64,
+    is_paused: bool,
+    onchain_aum_usd: u128,
+    onchain_aum_timestamp: i64,
+    asset_count: u32,
+    bump: u8,
+}
+
+impl ConfigFields {
+    /// Reads the fixed-size Borsh layout of `UnitasConfig`, skipping the fields the NAV doesn't
+    /// need.
+    fn parse(data: &[u8]) -> Result<Self, NavError> {
+        let mut reader = Reader(data);
+        if reader.take::<8>()? != CONFIG_DISCRIMINATOR {
+            return Err(NavError::InvalidDiscriminator);
+        }
+        if reader.take::<1>()?[0] != CONFIG_VERSION {
+            return Err(NavError::UnsupportedVersion);
+        }
+        let fund_id = u16::from_le_bytes(reader.take()?);
+        reader.skip(32 + 32)?; // admin, pending_admin
+        let aum_usd = u128::from_le_bytes(reader.take()?);
+        let last_updated_timestamp = i64::from_le_bytes(reader.take()?);
+        let last_updated_slot = u64::from_le_bytes(reader.take()?);
+        reader.skip(8)?; // aum_sequence
+        let aum_as_of = i64::from_le_bytes(reader.take()?);
+        let attested_aum_usd = u128::from_le_bytes(reader.take()?);
+        let usdu_supply = u128::from_le_bytes(reader.take()?);
+        let nav = u64::from_le_bytes(reader.take()?);
+        reader.skip(32)?; // reserves_root
+        reader.skip(1 + MAX_RESERVES_URI_LEN)?; // reserves_uri
+        reader.skip(32 + 16 + 8 + 32)?; // liabilities, usdu_config
+        let is_paused = reader.take::<1>()?[0] != 0;
+        reader.skip(8)?; // max_aum_age
+        let onchain_aum_usd = u128::from_le_bytes(reader.take()?);
+        let onchain_aum_timestamp = i64::from_le_bytes(reader.take()?);
+        let asset_count = u32::from_le_bytes(reader.take()?);
+        // collateral ratio, surplus and breaches, NAV peg band, deviation and off-peg time,
+        // has_price_feed
+        reader.skip(8 + 16 + 4 + 4 + 8 + 8 + 1)?;
+        let bump = reader.take::<1>()?[0];
+
+        Ok(ConfigFields {
+            fund_id,
+            aum_usd,
+            last_updated_timestamp,
+            last_updated_slot,
+            aum_as_of,
+            attested_aum_usd,
+            usdu_supply,
+            nav,
+            is_paused,
+            onchain_aum_usd,
+            onchain_aum_timestamp,
+            asset_count,
+            bump,
+        })
+    }
+
+    /// Mirrors `UnitasConfig::nav_as_of`.
+    fn nav_as_of(&self) -> i64 {
+        if self.asset_count > 0 {
+            self.aum_as_of.min(self.onchain_aum_timestamp)
+        } else {
+            self.aum_as_of
+        }
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], NavError> {
+        let (head, rest) = self
+            .0
+            .split_first_chunk::<N>()
+            .ok_or(NavError::InvalidData)?;
+        self.0 = rest;
+        Ok(*head)
+    }
+
+    fn skip(&mut self, len: usize) -> Result<(), NavError> {
+        self.0 = self.0.get(len..).ok_or(NavError::InvalidData)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::{AccountSerialize, Discriminator};
+    use unitas_oracle::state::{ReservesUri, UnitasConfig};
+
+    const NOW: i64 = 1_700_000_000;
+    const FUND_ID: u16 = 2;
+
+    fn config_pda(fund_id: u16) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[ADMIN_CONFIG_SEED, &unitas_oracle::state::fund_seed(fund_id)],
+            &PROGRAM_ID,
+        )
+    }
+
+    fn config() -> UnitasConfig {
+        UnitasConfig {
+            version: unitas_oracle::CONFIG_VERSION,
+            fund_id: FUND_ID,
+            bump: config_pda(FUND_ID).1,
+            aum_usd: 600_000_000,
+            attested_aum_usd: 100_000_000,
+            onchain_aum_usd: 300_000_000,
+            usdu_supply: 1_000_000_000,
+            nav: 1_000_000,
+            last_updated_timestamp: NOW - 10,
+            last_updated_slot: 42,
+            aum_as_of: NOW - 60,
+            reserves_uri: ReservesUri::new("https://unitas.example/reserves.json").unwrap(),
+            ..Default::default()
+        }
+    }
+
+    fn clock() -> Clock {
+        Clock {
+            unix_timestamp: NOW,
+            ..Default::default()
+        }
+    }
+
+    /// Serializes `config` with the program's own types into an account at `key` and loads it
+    /// through `load_nav` as the config of `FUND_ID`.
+    fn load(
+        config: &UnitasConfig,
+        key: Pubkey,
+        owner: Pubkey,
+        max_age: i64,
+    ) -> Result<Nav, NavError> {
+        let mut data = Vec::with_capacity(UnitasConfig::LEN);
+        config.try_serialize(&mut data).unwrap();
+        data.resize(UnitasConfig::LEN, 0);
+        let mut lamports = 1_000_000;
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        load_nav(&info, FUND_ID, max_age, &clock())
+    }
+
+    #[test]
+    fn constants_match_the_program() {
+        assert_eq!(PROGRAM_ID, unitas_oracle::ID);
+        assert_eq!(CONFIG_DISCRIMINATOR, UnitasConfig::DISCRIMINATOR);
+        assert_eq!(CONFIG_VERSION, unitas_oracle::CONFIG_VERSION);
+        assert_eq!(
+            ADMIN_CONFIG_SEED,
+            unitas_oracle::ADMIN_CONFIG_SEED.as_bytes()
+        );
+        assert_eq!(NAV_DECIMALS, unitas_oracle::NAV_DECIMALS);
+        assert_eq!(MAX_RESERVES_URI_LEN, unitas_oracle::MAX_RESERVES_URI_LEN);
+    }
+
+    #[test]
+    fn loads_fresh_nav() {
+        let (address, _) = config_pda(FUND_ID);
+        let nav = load(&config(), address, PROGRAM_ID, 3_600).unwrap();
+        assert_eq!(
+            nav,
+            Nav {
+                config: address,
+                fund_id: FUND_ID,
+                nav: 1_000_000,
+                aum_usd: 1_000_000_000,
+                usdu_supply: 1_000_000_000,
+                timestamp: NOW - 60,
+                slot: 42,
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_account_owned_by_another_program() {
+        let result = load(
+            &config(),
+            config_pda(FUND_ID).0,
+            Pubkey::new_unique(),
+            3_600,
+        );
+        assert_eq!(result, Err(NavError::InvalidOwner));
+    }
+
+    #[test]
+    fn rejects_config_at_another_address() {
+        let result = load(&config(), Pubkey::new_unique(), PROGRAM_ID, 3_600);
+        assert_eq!(result, Err(NavError::InvalidAddress));
+
+        // A genuine config of another fund, or one claiming another bump, is rejected too
+        let mut other_fund = config();
+        other_fund.fund_id = FUND_ID + 1;
+        let (other_address, bump) = config_pda(FUND_ID + 1);
+        other_fund.bump = bump;
+        let result = load(&other_fund, other_address, PROGRAM_ID, 3_600);
+        assert_eq!(result, Err(NavError::InvalidAddress));
+
+        let mut wrong_bump = config();
+        wrong_bump.bump = wrong_bump.bump.wrapping_sub(1);
+        let result = load(&wrong_bump, config_pda(FUND_ID).0, PROGRAM_ID, 3_600);
+        assert_eq!(result, Err(NavError::InvalidAddress));
+    }
+
+    #[test]
+    fn rejects_paused_oracle() {
+        let mut config = config();
+        config.is_paused = true;
+        let result = load(&config, config_pda(FUND_ID).0, PROGRAM_ID, 3_600);
+        assert_eq!(result, Err(NavError::Paused));
+    }
+
+    #[test]
+    fn rejects_nav_without_supply() {
+        let mut config = config();
+        config.usdu_supply = 0;
+        let result = load(&config, config_pda(FUND_ID).0, PROGRAM_ID, 3_600);
+        assert_eq!(result, Err(NavError::Unavailable));
+    }
+
+    #[test]
+    fn rejects_stale_nav() {
+        let result = load(&config(), config_pda(FUND_ID).0, PROGRAM_ID, 59);
+        assert_eq!(result, Err(NavError::Stale));
+    }
+
+    #[test]
+    fn ages_nav_from_the_last_valuation_round() {
+        let mut config = config();
+        config.asset_count = 1;
+        config.onchain_aum_timestamp = NOW - 120;
+        assert_eq!(
+            load(&config, config_pda(FUND_ID).0, PROGRAM_ID, 119),
+            Err(NavError::Stale)
+        );
+        let nav = load(&config, config_pda(FUND_ID).0, PROGRAM_ID, 120).unwrap();
+        assert_eq!(nav.timestamp, NOW - 120);
+    }
+
+    #[test]
+    fn rejects_unknown_version() {
+        let mut config = config();
+        config.version = unitas_oracle::CONFIG_VERSION + 1;
+        let result = load(&config, config_pda(FUND_ID).0, PROGRAM_ID, 3_600);
+        assert_eq!(result, Err(NavError::UnsupportedVersion));
+    }
+}
@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::state::{fund_seed, Operator, UnitasConfig};
+use crate::OPERATOR_SEED;
+
+/// Authorises `user` as the config admin or as one of its registered operators.
+///
+/// Operator-gated instructions take the operator account unchecked so the admin does not
+/// need one; for everyone else this verifies it is the `[OPERATOR_SEED, fund_seed, user]` PDA,
+/// owned by this program, carrying the `Operator` discriminator and recording `user`.
+pub fn require_admin_or_operator(
+    config: &Account<UnitasConfig>,
+    user: &Pubkey,
+    operator: &AccountInfo,
+) -> Result<()> {
+    if config.is_admin(user) {
+        return Ok(());
+    }
+
+    let (operator_address, _) = Pubkey::find_program_address(
+        &[
+            OPERATOR_SEED.as_bytes(),
+            fund_seed(config.fund_id).as_ref(),
+            user.as_ref(),
+        ],
+        &crate::ID,
+    );
+    require_keys_eq!(operator.key(), operator_address, ErrorCode::InvalidOperator);
+    require_keys_eq!(*operator.owner, crate::ID, ErrorCode::InvalidOperator);
+
+    let data = operator.try_borrow_data()?;
+    let operator_account = Operator::try_deserialize(&mut &data[..])?;
+    require_keys_eq!(operator_account.user, *user, ErrorCode::InvalidOperator);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::error::ErrorCode as AnchorErrorCode;
+
+    const ADMIN: Pubkey = Pubkey::new_from_array([1; 32]);
+    const CONFIG: Pubkey = Pubkey::new_from_array([2; 32]);
+
+    fn operator_data(user: Pubkey) -> Vec<u8> {
+        let mut data = Vec::with_capacity(Operator::LEN);
+        Operator { user }.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    fn operator_address(fund_id: u16, user: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                OPERATOR_SEED.as_bytes(),
+                fund_seed(fund_id).as_ref(),
+                user.as_ref(),
+            ],
+            &crate::ID,
+        )
+        .0
+    }
+
+    /// Runs the guard for `user` against an operator account with the given address,
+    /// owner and data, under the fund 0 config administered by `ADMIN` at `CONFIG`.
+    fn check(user: Pubkey, key: Pubkey, owner: Pubkey, mut data: Vec<u8>) -> Result<()> {
+        let mut config_lamports = 1_000_000;
+        let mut config_data = Vec::with_capacity(UnitasConfig::LEN);
+        UnitasConfig {
+            admin: ADMIN,
+            ..Default::default()
+        }
+        .try_serialize(&mut config_data)
+        .unwrap();
+        let program_id = crate::ID;
+        let config_info = AccountInfo::new(
+            &CONFIG,
+            false,
+            false,
+            &mut config_lamports,
+            &mut config_data,
+            &program_id,
+            false,
+            0,
+        );
+        let config = Account::<UnitasConfig>::try_from(&config_info).unwrap();
+
+        let mut lamports = 1_000_000;
+        let operator = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        require_admin_or_operator(&config, &user, &operator)
+    }
+
+    #[test]
+    fn admin_needs_no_operator_account() {
+        let result = check(ADMIN, Pubkey::new_unique(), Pubkey::default(), vec![]);
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn accepts_registered_operator() {
+        let user = Pubkey::new_unique();
+        let key = operator_address(0, &user);
+        assert!(check(user, key, crate::ID, operator_data(user)).is_ok());
+    }
+
+    #[test]
+    fn rejects_forged_account_owned_by_another_program() {
+        let user = Pubkey::new_unique();
+        let key = operator_address(0, &user);
+        let result = check(user, key, Pubkey::new_unique(), operator_data(user));
+        assert_eq!(result.unwrap_err(), error!(ErrorCode::InvalidOperator));
+    }
+
+    #[test]
+    fn rejects_non_pda_account_with_operator_bytes() {
+        let user = Pubkey::new_unique();
+        let result = check(user, Pubkey::new_unique(), crate::ID, operator_data(user));
+        assert_eq!(result.unwrap_err(), error!(ErrorCode::InvalidOperator));
+    }
+
+    #[test]
+    fn rejects_operator_of_another_fund() {
+        let user = Pubkey::new_unique();
+        let key = operator_address(1, &user);
+        let result = check(user, key, crate::ID, operator_data(user));
+        assert_eq!(result.unwrap_err(), error!(ErrorCode::InvalidOperator));
+    }
+
+    #[test]
+    fn rejects_operator_pda_of_another_user() {
+        let user = Pubkey::new_unique();
+        let other = Pubkey::new_unique();
+        let key = operator_address(0, &other);
+        let result = check(user, key, crate::ID, operator_data(other));
+        assert_eq!(result.unwrap_err(), error!(ErrorCode::InvalidOperator));
+    }
+
+    #[test]
+    fn rejects_program_account_with_wrong_discriminator() {
+        let user = Pubkey::new_unique();
+        let mut data = operator_data(user);
+        data[..8].copy_from_slice(UnitasConfig::DISCRIMINATOR);
+        let key = operator_address(0, &user);
+        let result = check(user, key, crate::ID, data);
+        assert_eq!(
+            result.unwrap_err(),
+            error!(AnchorErrorCode::AccountDiscriminatorMismatch)
+        );
+    }
+}
@@ -8,3 +8,76 @@ pub const OPERATOR_SEED: &str = "operator";
 
 #[constant]
 pub const ASSET_LOOKUP_TABLE_SEED: &str = "asset-lookup-table";
+
+#[constant]
+pub const ACCOUNT_LABEL_SEED: &str = "account-label";
+
+#[constant]
+pub const CUSTODIAN_SEED: &str = "custodian";
+
+/// Domain prefix of the balance statement a custodian signs with its Ethereum key.
+pub const CUSTODIAN_ATTESTATION_DOMAIN: &[u8] = b"unitas-oracle:custodian-balance:v1";
+
+/// Maximum age, in seconds, of a custodian balance statement when it is submitted.
+pub const MAX_CUSTODIAN_ATTESTATION_AGE: i64 = 86_400;
+
+/// Maximum length, in bytes, of the reserves report URI committed with an AUM update.
+pub const MAX_RESERVES_URI_LEN: usize = 200;
+
+/// Decimals of the published NAV. AUM values are scaled to 6 decimals and USDU has 6 decimals,
+/// so a NAV of 1.0 is `10^NAV_DECIMALS`.
+#[constant]
+pub const NAV_DECIMALS: u8 = 6;
+
+/// Layout version of the `NavData` returned by `get_nav`.
+#[constant]
+pub const NAV_DATA_VERSION: u8 = 1;
+
+#[constant]
+pub const USDU_PRICE_FEED_SEED: &str = "usdu-price-feed";
+
+/// Number of NAV publications the USDU price feed EMA is smoothed over.
+pub const NAV_EMA_WINDOW: u64 = 8;
+
+/// Basis points in 100%.
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Collateral ratios, in basis points, whose crossing emits a `CollateralRatioThresholdCrossed`
+/// event. At most 32, one bit each in `UnitasConfig::collateral_breaches`.
+pub const COLLATERAL_RATIO_THRESHOLDS_BPS: [u64; 2] = [10_000, 10_200];
+
+/// Current layout version of `UnitasConfig`.
+#[constant]
+pub const CONFIG_VERSION: u8 = 1;
+
+/// Current layout version of `AssetLookupTable`.
+#[constant]
+pub const ASSET_LOOKUP_TABLE_VERSION: u8 = 1;
+
+#[constant]
+pub const VALUATION_ROUND_SEED: &str = "valuation-round";
+
+/// Maximum time, in seconds, between beginning a valuation round and finalizing it.
+pub const MAX_VALUATION_ROUND_DURATION: i64 = 300;
+
+/// Maximum age, in seconds, of an oracle price used to value an asset on chain.
+pub const MAX_ORACLE_PRICE_AGE: i64 = 60;
+
+/// Asset "mint" of tables valuing the native SOL balance of their owners, above rent exemption.
+pub const NATIVE_SOL_ASSET_MINT: Pubkey = anchor_lang::system_program::ID;
+
+/// Asset "mint" of tables valuing stake accounts, tracked in place of owners.
+pub const STAKE_ASSET_MINT: Pubkey = STAKE_PROGRAM_ID;
+
+pub const STAKE_PROGRAM_ID: Pubkey = pubkey!("Stake11111111111111111111111111111111111111");
+
+pub const SOL_DECIMALS: u8 = 9;
+
+pub const SPL_STAKE_POOL_PROGRAM_ID: Pubkey =
+    pubkey!("SPoo1Ku8WFXoNDMHPsrGSTSG1Y47rzgn41SLUNakuHy");
+
+/// Pyth receiver program, owner of the `PriceUpdateV2` accounts assets are priced with.
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+/// Default maximum age, in seconds, of the AUM before the NAV is reported as stale.
+pub const DEFAULT_MAX_AUM_AGE: i64 = 86_400;
@@ -22,4 +22,90 @@ pub enum ErrorCode {
     ProposedAdminAlreadySet,
     #[msg("Proposed admin is current admin")]
     ProposedAdminIsCurrentAdmin,
+    #[msg("Invalid custodian")]
+    InvalidCustodian,
+    #[msg("Missing secp256k1 instruction")]
+    MissingSecp256k1Instruction,
+    #[msg("Invalid secp256k1 instruction")]
+    InvalidSecp256k1Instruction,
+    #[msg("Attestation does not match the signed message")]
+    AttestationMismatch,
+    #[msg("Stale attestation")]
+    StaleAttestation,
+    #[msg("Math overflow")]
+    MathOverflow,
+    #[msg("Reserves report URI too long")]
+    ReservesUriTooLong,
+    #[msg("Invalid USDU config")]
+    InvalidUsduConfig,
+    #[msg("Invalid max AUM age")]
+    InvalidMaxAumAge,
+    #[msg("Oracle is paused")]
+    OraclePaused,
+    #[msg("NAV is not available")]
+    NavUnavailable,
+    #[msg("NAV is stale")]
+    StaleNav,
+    #[msg("Collateral ratio below minimum")]
+    CollateralRatioBelowMinimum,
+    #[msg("AUM sequence mismatch")]
+    AumSequenceMismatch,
+    #[msg("AUM update is not newer than the stored one")]
+    OutdatedAumUpdate,
+    #[msg("Account already uses the current layout")]
+    AccountAlreadyMigrated,
+    #[msg("Unknown account layout")]
+    UnknownAccountLayout,
+    #[msg("Valuation is older than the cached one")]
+    OutdatedValuation,
+    #[msg("A valuation round is already in progress")]
+    ValuationRoundInProgress,
+    #[msg("Valuation round is finalized or expired")]
+    ValuationRoundClosed,
+    #[msg("Not every registered asset was accumulated in the round")]
+    ValuationIncomplete,
+    #[msg("Asset already accumulated in this round")]
+    AssetAlreadyAccumulated,
+    #[msg("Invalid oracle account")]
+    InvalidOracle,
+    #[msg("Oracle price is stale")]
+    StaleOraclePrice,
+    #[msg("Invalid oracle price")]
+    InvalidOraclePrice,
+    #[msg("Invalid token account")]
+    InvalidTokenAccount,
+    #[msg("No accounts provided")]
+    EmptyAccountList,
+    #[msg("Account listed more than once")]
+    DuplicateAccount,
+    #[msg("Account label is too long")]
+    AccountLabelTooLong,
+    #[msg("Unknown asset kind")]
+    UnknownAssetKind,
+    #[msg("Invalid stake account")]
+    InvalidStakeAccount,
+    #[msg("Decimals do not match the asset")]
+    InvalidDecimals,
+    #[msg("Invalid stake pool")]
+    InvalidStakePool,
+    #[msg("Stake pool exchange rate is not updated for the current epoch")]
+    StaleStakePool,
+    #[msg("Price path must have between one and MAX_PRICE_LEGS legs")]
+    InvalidPricePath,
+    #[msg("Peg band must be at most 100%")]
+    InvalidPegBand,
+    #[msg("Unknown pricing mode")]
+    UnknownPricingMode,
+    #[msg("Valuation does not use the asset's pricing mode")]
+    PricingModeMismatch,
+    #[msg("The USDU price feed must be passed once it has been created")]
+    PriceFeedRequired,
+    #[msg("Remove the asset table's accounts before closing it")]
+    AssetLookupTableNotEmpty,
+    #[msg("Attestation has not expired")]
+    AttestationNotExpired,
+    #[msg("Unknown valuation source")]
+    UnknownValuationSource,
+    #[msg("Oracle feed ids don't match the asset's price path")]
+    PriceFeedIdMismatch,
 }
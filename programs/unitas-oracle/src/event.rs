@@ -1,63 +1,307 @@
 use anchor_lang::prelude::*;
 
+use crate::state::{AccountCategory, NavPegDirection, PriceLeg, PricingMode, ValuationSource};
+
 /// Admin config
 #[event]
+#[derive(Debug)]
 pub struct AdminConfigCreated {
     pub admin: Pubkey,
     pub config: Pubkey,
+    pub fund_id: u16,
 }
 
 #[event]
+#[derive(Debug)]
 pub struct OperatorAdded {
     pub operator: Pubkey,
     pub user: Pubkey,
 }
 
 #[event]
+#[derive(Debug)]
 pub struct OperatorRemoved {
     pub operator: Pubkey,
     pub user: Pubkey,
 }
 
 #[event]
+#[derive(Debug)]
 pub struct AdminTransferProposed {
     pub current_admin: Pubkey,
     pub proposed_admin: Pubkey,
 }
 
 #[event]
+#[derive(Debug)]
 pub struct AdminTransferAccepted {
     pub current_admin: Pubkey,
     pub proposed_admin: Pubkey,
 }
 
 #[event]
+#[derive(Debug)]
 pub struct AdminTransferCompleted {
     pub previous_admin: Pubkey,
     pub new_admin: Pubkey,
 }
 
+#[event]
+#[derive(Debug)]
+pub struct PausedUpdated {
+    pub paused: bool,
+    pub config: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct MaxAumAgeUpdated {
+    pub max_aum_age: i64,
+    pub config: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct NavPegBandUpdated {
+    pub nav_peg_band_bps: u32,
+    pub config: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct UsduPriceFeedCreated {
+    pub price_feed: Pubkey,
+    pub feed_id: [u8; 32],
+}
+
 /// Asset lookup table
 #[event]
+#[derive(Debug)]
 pub struct AssetLookupTableCreated {
     pub lookup_table: Pubkey,
 }
 
 #[event]
+#[derive(Debug)]
+pub struct AssetLookupTableClosed {
+    pub lookup_table: Pubkey,
+    pub asset_mint: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ConfigMigrated {
+    pub config: Pubkey,
+    pub version: u8,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct AssetLookupTableMigrated {
+    pub lookup_table: Pubkey,
+    pub version: u8,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct AssetPricePathSet {
+    pub lookup_table: Pubkey,
+    pub legs: Vec<PriceLeg>,
+    pub feed_ids: Vec<[u8; 32]>,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct AssetPegBandSet {
+    pub lookup_table: Pubkey,
+    pub peg_band_bps: u16,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct AssetPricingModeSet {
+    pub lookup_table: Pubkey,
+    pub pricing_mode: PricingMode,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct AssetStakePoolSet {
+    pub lookup_table: Pubkey,
+    // Default when the table is priced with its oracle alone
+    pub stake_pool: Pubkey,
+}
+
+#[event]
+#[derive(Debug)]
 pub struct AccountAdded {
     pub account: Pubkey,
     pub lookup_table: Pubkey,
 }
 
 #[event]
+#[derive(Debug)]
 pub struct AccountRemoved {
     pub account: Pubkey,
     pub lookup_table: Pubkey,
 }
 
 #[event]
+#[derive(Debug)]
+pub struct AccountLabelSet {
+    pub config: Pubkey,
+    pub account: Pubkey,
+    pub label: String,
+    pub category: AccountCategory,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct AssetValuationRecorded {
+    pub lookup_table: Pubkey,
+    pub asset_mint: Pubkey,
+    pub price: i64,
+    pub price_exponent: i32,
+    pub pricing_mode: PricingMode,
+    pub total_amount: u64,
+    pub usd_value: u64,
+    pub valued_at: i64,
+    pub source: ValuationSource,
+}
+
+/// A pegged stablecoin's price left its band; it is valued at that price until it returns.
+#[event]
+#[derive(Debug)]
+pub struct StablecoinDepegged {
+    pub lookup_table: Pubkey,
+    pub asset_mint: Pubkey,
+    pub price: i64,
+    pub price_exponent: i32,
+    pub peg_band_bps: u16,
+}
+
+/// A depegged stablecoin's price is back within its band; it is valued at $1 again.
+#[event]
+#[derive(Debug)]
+pub struct StablecoinRepegged {
+    pub lookup_table: Pubkey,
+    pub asset_mint: Pubkey,
+    pub price: i64,
+    pub price_exponent: i32,
+    pub peg_band_bps: u16,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ValuationRoundStarted {
+    pub config: Pubkey,
+    pub round: u64,
+    pub asset_count: u32,
+    pub started_at: i64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct ValuationRoundFinalized {
+    pub config: Pubkey,
+    pub round: u64,
+    pub onchain_aum_usd: u128,
+    pub onchain_aum_timestamp: i64,
+    pub usdu_supply: u128,
+    pub nav: u64,
+}
+
+/// The collateral ratio fell below `threshold_bps`, or recovered to it when `is_below` is false.
+#[event]
+#[derive(Debug)]
+pub struct CollateralRatioThresholdCrossed {
+    pub config: Pubkey,
+    pub threshold_bps: u64,
+    pub is_below: bool,
+    pub collateral_ratio_bps: u64,
+    pub collateral_surplus_usd: i128,
+}
+
+/// A published NAV was more than `peg_band_bps` away from 1.0; `off_peg_since` is when it
+/// left the band.
+#[event]
+#[derive(Debug)]
+pub struct NavPegDeviation {
+    pub config: Pubkey,
+    pub nav: u64,
+    pub direction: NavPegDirection,
+    pub deviation_bps: u64,
+    pub peg_band_bps: u32,
+    pub off_peg_since: i64,
+}
+
+/// The published NAV is back within `peg_band_bps` of 1.0 after leaving it at `off_peg_since`.
+#[event]
+#[derive(Debug)]
+pub struct NavPegRestored {
+    pub config: Pubkey,
+    pub nav: u64,
+    pub peg_band_bps: u32,
+    pub off_peg_since: i64,
+}
+
+#[event]
+#[derive(Debug)]
 pub struct AumUsdUpdated {
     pub aum_usd: u128,
     pub last_updated_timestamp: i64,
+    pub last_updated_slot: u64,
+    pub aum_sequence: u64,
+    pub aum_as_of: i64,
+    pub usdu_supply: u128,
+    pub nav: u64,
+    pub config: Pubkey,
+    pub reserves_root: [u8; 32],
+    pub reserves_uri: String,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct LiabilitiesCommitted {
+    pub liabilities_root: [u8; 32],
+    pub total_liabilities: u128,
+    pub timestamp: i64,
     pub config: Pubkey,
 }
+
+/// Custodian attestations
+#[event]
+#[derive(Debug)]
+pub struct CustodianAdded {
+    pub custodian: Pubkey,
+    pub eth_address: [u8; 20],
+}
+
+#[event]
+#[derive(Debug)]
+pub struct CustodianRemoved {
+    pub custodian: Pubkey,
+    pub eth_address: [u8; 20],
+}
+
+#[event]
+#[derive(Debug)]
+pub struct CustodianBalanceAttested {
+    pub custodian: Pubkey,
+    pub eth_address: [u8; 20],
+    pub balance_usd: u128,
+    pub timestamp: i64,
+    pub attested_aum_usd: u128,
+    pub nav: u64,
+}
+
+#[event]
+#[derive(Debug)]
+pub struct CustodianAttestationExpired {
+    pub custodian: Pubkey,
+    pub eth_address: [u8; 20],
+    pub balance_usd: u128,
+    pub timestamp: i64,
+    pub attested_aum_usd: u128,
+    pub nav: u64,
+}
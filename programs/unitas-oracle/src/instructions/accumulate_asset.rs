@@ -0,0 +1,168 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::event::AssetValuationRecorded;
+use crate::state::{
+    compose_price, fund_seed, usd_value, AssetLookupTable, PriceFeedMessage, PriceLeg,
+    PriceUpdateV2, StakePoolRate, UnitasConfig, ValuationRound, ValuationSource,
+};
+use crate::{ADMIN_CONFIG_SEED, ASSET_LOOKUP_TABLE_SEED, VALUATION_ROUND_SEED};
+
+/// Values one asset table into the open round. The remaining accounts are the oracles of the
+/// table's price path after `oracle`, then the holding accounts of its owners, see
+/// `AssetLookupTable::holding_address`, both in the table's order.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AccumulateAsset<'info> {
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump
+    )]
+    pub config: Account<'info, UnitasConfig>,
+    #[account(
+        mut,
+        seeds = [VALUATION_ROUND_SEED.as_bytes(), config.key().as_ref()],
+        bump
+    )]
+    pub valuation_round: Account<'info, ValuationRound>,
+    #[account(
+        mut,
+        seeds = [
+            ASSET_LOOKUP_TABLE_SEED.as_bytes(),
+            fund_seed(config.fund_id).as_ref(),
+            asset_lookup_table.load()?.asset_mint.as_ref()
+        ],
+        bump
+    )]
+    pub asset_lookup_table: AccountLoader<'info, AssetLookupTable>,
+    /// CHECK: First oracle of the table's price path, validated by `load_pyth_price`
+    #[account(address = asset_lookup_table.load()?.oracle_account @ ErrorCode::InvalidOracle)]
+    pub oracle: UncheckedAccount<'info>,
+    /// CHECK: Stake pool named by the table, required when it has one; validated by
+    /// `StakePoolRate::load`
+    #[account(address = asset_lookup_table.load()?.stake_pool @ ErrorCode::InvalidStakePool)]
+    pub stake_pool: Option<UncheckedAccount<'info>>,
+}
+
+pub fn process_accumulate_asset(ctx: Context<AccumulateAsset>) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let mut asset_lookup_table = ctx.accounts.asset_lookup_table.load_mut()?;
+
+    let price_path = asset_lookup_table.price_path();
+    require_gte!(
+        ctx.remaining_accounts.len(),
+        price_path.len() - 1,
+        ErrorCode::InvalidOracle
+    );
+    let (legs, holdings) = ctx.remaining_accounts.split_at(price_path.len() - 1);
+    let pricing_mode = asset_lookup_table.pricing_mode()?;
+    // Every leg must be fresh, not only the first
+    let mut messages = Vec::with_capacity(price_path.len());
+    messages.push(load_leg(&ctx.accounts.oracle, &price_path[0], now)?);
+    for (leg, oracle) in price_path[1..].iter().zip(legs) {
+        messages.push(load_leg(oracle, leg, now)?);
+    }
+    // Each oracle must publish the feed its leg was set with, not just any fresh price
+    let feed_ids: Vec<[u8; 32]> = messages.iter().map(|message| message.feed_id).collect();
+    asset_lookup_table.check_price_feed_ids(&feed_ids)?;
+    let (path_price, price_exponent) =
+        compose_price(price_path.iter().zip(&messages).map(|(leg, message)| {
+            (
+                pricing_mode.leg_price(message, leg.divide),
+                message.exponent,
+                leg.divide,
+            )
+        }))?;
+
+    // Liquid staking tokens are priced from SOL through their pool's exchange rate
+    let oracle_price = if asset_lookup_table.stake_pool == Pubkey::default() {
+        path_price
+    } else {
+        let stake_pool = ctx
+            .accounts
+            .stake_pool
+            .as_ref()
+            .ok_or(ErrorCode::InvalidStakePool)?;
+        StakePoolRate::load(stake_pool)?.pool_token_price(path_price, clock.epoch)?
+    };
+    // Pegged stablecoins are worth exactly $1 while their price stays within the band
+    let (asset_price, peg_change) = asset_lookup_table.update_peg(oracle_price, price_exponent)?;
+    let peg_band_bps = asset_lookup_table.peg_band_bps;
+    let valuation_round = &mut ctx.accounts.valuation_round;
+    require!(
+        asset_lookup_table.last_accumulated_round != valuation_round.round,
+        ErrorCode::AssetAlreadyAccumulated
+    );
+
+    let asset_kind = asset_lookup_table.asset_kind()?;
+    let rent = Rent::get()?;
+    let owners = &asset_lookup_table.token_account_owners
+        [..asset_lookup_table.token_account_owners_len as usize];
+    require_eq!(holdings.len(), owners.len(), ErrorCode::InvalidTokenAccount);
+    let mut total_amount: u64 = 0;
+    for (owner, holding) in owners.iter().zip(holdings) {
+        require_keys_eq!(
+            holding.key(),
+            asset_lookup_table.holding_address(owner)?,
+            ErrorCode::InvalidTokenAccount
+        );
+        let amount = asset_kind.holding_amount(
+            holding.owner,
+            holding.lamports(),
+            &holding.try_borrow_data()?,
+            &rent,
+        )?;
+        total_amount = total_amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+    }
+
+    let value = usd_value(
+        total_amount,
+        asset_lookup_table.decimals,
+        asset_price,
+        price_exponent,
+    )?;
+    valuation_round.accumulate(value, now)?;
+    asset_lookup_table.last_accumulated_round = valuation_round.round;
+    asset_lookup_table.record_valuation(
+        asset_price,
+        price_exponent,
+        total_amount,
+        value,
+        now,
+        ValuationSource::OnChain,
+    )?;
+    let asset_mint = asset_lookup_table.asset_mint;
+    drop(asset_lookup_table);
+
+    emit_event!(
+        ctx,
+        AssetValuationRecorded {
+            lookup_table: ctx.accounts.asset_lookup_table.key(),
+            asset_mint,
+            price: asset_price,
+            price_exponent,
+            pricing_mode,
+            total_amount,
+            usd_value: value,
+            valued_at: now,
+            source: ValuationSource::OnChain,
+        }
+    );
+    emit_peg_change!(
+        ctx,
+        peg_change,
+        asset_mint,
+        oracle_price,
+        price_exponent,
+        peg_band_bps
+    );
+    Ok(())
+}
+
+fn load_leg(oracle: &AccountInfo, leg: &PriceLeg, now: i64) -> Result<PriceFeedMessage> {
+    require_keys_eq!(oracle.key(), leg.oracle, ErrorCode::InvalidOracle);
+    PriceUpdateV2::load_pyth_price(oracle, now)
+}
@@ -1,11 +1,11 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
 
-use crate::error::ErrorCode;
+use crate::access_control::require_admin_or_operator;
 use crate::event::AccountAdded;
-use crate::state::{AssetLookupTable, Operator, UnitasConfig};
+use crate::state::{fund_seed, AssetLookupTable, UnitasConfig};
 use crate::{ADMIN_CONFIG_SEED, ASSET_LOOKUP_TABLE_SEED};
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(account: Pubkey)]
 pub struct AddAccount<'info> {
@@ -14,47 +14,47 @@ pub struct AddAccount<'info> {
 
     #[account(
         mut,
-        seeds = [ASSET_LOOKUP_TABLE_SEED.as_bytes(), asset_mint.key().as_ref()],
+        seeds = [
+            ASSET_LOOKUP_TABLE_SEED.as_bytes(),
+            fund_seed(config.fund_id).as_ref(),
+            asset_mint.key().as_ref()
+        ],
         bump
     )]
     pub asset_lookup_table: AccountLoader<'info, AssetLookupTable>,
 
-    pub asset_mint: Account<'info, Mint>,
+    /// CHECK: Only used to derive the table address; may be a pseudo mint, see `AssetKind`
+    pub asset_mint: UncheckedAccount<'info>,
 
     #[account(
         mut,
-        seeds = [ADMIN_CONFIG_SEED.as_bytes()],
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
         bump
     )]
     pub config: Account<'info, UnitasConfig>,
 
-    /// CHECK: This is the operator account, it is checked in the instruction
+    /// CHECK: Validated by `require_admin_or_operator` unless the user is the admin
     pub operator: UncheckedAccount<'info>,
 
     pub system_program: Program<'info, System>,
 }
 
 pub fn process_add_account(ctx: Context<AddAccount>, account: Pubkey) -> Result<()> {
-    if !ctx.accounts.config.is_admin(&ctx.accounts.user.key()) {
-        let mut data: &[u8] = &ctx.accounts.operator.data.borrow();
-        let operator_account = Operator::try_deserialize_unchecked(&mut data)?;
-        require!(
-            operator_account.user == ctx.accounts.user.key(),
-            ErrorCode::InvalidOperator
-        );
-    } else {
-        require!(
-            ctx.accounts.config.is_admin(&ctx.accounts.user.key()),
-            ErrorCode::InvalidAdmin
-        );
-    }
+    require_admin_or_operator(
+        &ctx.accounts.config,
+        &ctx.accounts.user.key(),
+        &ctx.accounts.operator,
+    )?;
 
     let mut asset_lookup_table = ctx.accounts.asset_lookup_table.load_mut()?;
     asset_lookup_table.add_token_account_owner(account)?;
 
-    emit!(AccountAdded {
-        account,
-        lookup_table: ctx.accounts.asset_lookup_table.key()
-    });
+    emit_event!(
+        ctx,
+        AccountAdded {
+            account,
+            lookup_table: ctx.accounts.asset_lookup_table.key()
+        }
+    );
     Ok(())
 }
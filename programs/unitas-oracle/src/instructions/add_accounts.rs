@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::access_control::require_admin_or_operator;
+use crate::event::AccountAdded;
+use crate::state::{fund_seed, AssetLookupTable, UnitasConfig};
+use crate::{ADMIN_CONFIG_SEED, ASSET_LOOKUP_TABLE_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct AddAccounts<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            ASSET_LOOKUP_TABLE_SEED.as_bytes(),
+            fund_seed(config.fund_id).as_ref(),
+            asset_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub asset_lookup_table: AccountLoader<'info, AssetLookupTable>,
+
+    /// CHECK: Only used to derive the table address; may be a pseudo mint, see `AssetKind`
+    pub asset_mint: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump
+    )]
+    pub config: Account<'info, UnitasConfig>,
+
+    /// CHECK: Validated by `require_admin_or_operator` unless the user is the admin
+    pub operator: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_add_accounts(ctx: Context<AddAccounts>, accounts: Vec<Pubkey>) -> Result<()> {
+    require_admin_or_operator(
+        &ctx.accounts.config,
+        &ctx.accounts.user.key(),
+        &ctx.accounts.operator,
+    )?;
+
+    let mut asset_lookup_table = ctx.accounts.asset_lookup_table.load_mut()?;
+    asset_lookup_table.add_token_account_owners(&accounts)?;
+
+    for account in accounts {
+        emit_event!(
+            ctx,
+            AccountAdded {
+                account,
+                lookup_table: ctx.accounts.asset_lookup_table.key()
+            }
+        );
+    }
+    Ok(())
+}
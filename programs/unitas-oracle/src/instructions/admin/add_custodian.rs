@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::event::CustodianAdded;
+use crate::state::{fund_seed, Custodian, UnitasConfig, ETH_ADDRESS_LEN};
+use crate::{ADMIN_CONFIG_SEED, CUSTODIAN_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(eth_address: [u8; ETH_ADDRESS_LEN])]
+pub struct AddCustodian<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump,
+        constraint = config.is_admin(&admin.key()) @ ErrorCode::InvalidAdmin
+    )]
+    pub config: Account<'info, UnitasConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = Custodian::LEN,
+        seeds = [CUSTODIAN_SEED.as_bytes(), config.key().as_ref(), eth_address.as_ref()],
+        bump
+    )]
+    pub custodian: Account<'info, Custodian>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_add_custodian(
+    ctx: Context<AddCustodian>,
+    eth_address: [u8; ETH_ADDRESS_LEN],
+) -> Result<()> {
+    require!(
+        eth_address != [0u8; ETH_ADDRESS_LEN],
+        ErrorCode::InvalidCustodian
+    );
+    #[cfg(feature = "enable-log")]
+    msg!(
+        "add_custodian: admin:{}, custodian:{}",
+        ctx.accounts.admin.key(),
+        ctx.accounts.custodian.key()
+    );
+
+    ctx.accounts.custodian.set_inner(Custodian {
+        eth_address,
+        attested_balance_usd: 0,
+        attested_timestamp: 0,
+    });
+    emit_event!(
+        ctx,
+        CustodianAdded {
+            custodian: ctx.accounts.custodian.key(),
+            eth_address,
+        }
+    );
+    Ok(())
+}
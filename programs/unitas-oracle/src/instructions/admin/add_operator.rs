@@ -2,9 +2,10 @@ use anchor_lang::prelude::*;
 
 use crate::error::ErrorCode;
 use crate::event::OperatorAdded;
-use crate::state::{Operator, UnitasConfig};
+use crate::state::{fund_seed, Operator, UnitasConfig};
 use crate::{ADMIN_CONFIG_SEED, OPERATOR_SEED};
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(user: Pubkey)]
 pub struct AddOperator<'info> {
@@ -12,7 +13,7 @@ pub struct AddOperator<'info> {
     pub admin: Signer<'info>,
     #[account(
         mut,
-        seeds = [ADMIN_CONFIG_SEED.as_bytes()],
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
         bump,
         constraint = config.is_admin(&admin.key()) @ ErrorCode::InvalidAdmin
     )]
@@ -21,7 +22,7 @@ pub struct AddOperator<'info> {
         init_if_needed,
         payer = admin,
         space = Operator::LEN,
-        seeds = [OPERATOR_SEED.as_bytes(), user.as_ref()],
+        seeds = [OPERATOR_SEED.as_bytes(), fund_seed(config.fund_id).as_ref(), user.as_ref()],
         bump
     )]
     pub operator: Account<'info, Operator>,
@@ -39,9 +40,12 @@ pub fn process_add_operator(ctx: Context<AddOperator>, user: Pubkey) -> Result<(
     );
 
     ctx.accounts.operator.set_inner(operator);
-    emit!(OperatorAdded {
-        user,
-        operator: ctx.accounts.operator.key(),
-    });
+    emit_event!(
+        ctx,
+        OperatorAdded {
+            user,
+            operator: ctx.accounts.operator.key(),
+        }
+    );
     Ok(())
 }
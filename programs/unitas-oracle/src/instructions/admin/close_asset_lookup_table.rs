@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::event::AssetLookupTableClosed;
+use crate::state::{fund_seed, AssetLookupTable, UnitasConfig};
+use crate::{ADMIN_CONFIG_SEED, ASSET_LOOKUP_TABLE_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseAssetLookupTable<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump,
+        constraint = config.is_admin(&admin.key()) @ ErrorCode::InvalidAdmin
+    )]
+    pub config: Account<'info, UnitasConfig>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [
+            ASSET_LOOKUP_TABLE_SEED.as_bytes(),
+            fund_seed(config.fund_id).as_ref(),
+            asset_lookup_table.load()?.asset_mint.as_ref()
+        ],
+        bump
+    )]
+    pub asset_lookup_table: AccountLoader<'info, AssetLookupTable>,
+}
+
+/// Unregisters an asset table so valuation rounds no longer wait for it. Its accounts must be
+/// removed first, so closing it can't silently drop holdings from the on-chain AUM; the last
+/// finalized `onchain_aum_usd` keeps its value until the next round.
+pub fn process_close_asset_lookup_table(ctx: Context<CloseAssetLookupTable>) -> Result<()> {
+    let asset_lookup_table = ctx.accounts.asset_lookup_table.load()?;
+    require_eq!(
+        asset_lookup_table.token_account_owners_len,
+        0,
+        ErrorCode::AssetLookupTableNotEmpty
+    );
+    let asset_mint = asset_lookup_table.asset_mint;
+    drop(asset_lookup_table);
+
+    let config = &mut ctx.accounts.config;
+    config.asset_count = config
+        .asset_count
+        .checked_sub(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit_event!(
+        ctx,
+        AssetLookupTableClosed {
+            lookup_table: ctx.accounts.asset_lookup_table.key(),
+            asset_mint,
+        }
+    );
+    Ok(())
+}
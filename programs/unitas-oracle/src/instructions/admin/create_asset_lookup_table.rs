@@ -1,10 +1,12 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::Mint;
+use anchor_spl::token::{self, Mint};
 
 use crate::error::ErrorCode;
-use crate::state::{AssetLookupTable, UnitasConfig};
-use crate::{ADMIN_CONFIG_SEED, ASSET_LOOKUP_TABLE_SEED};
+use crate::event::AssetLookupTableCreated;
+use crate::state::{fund_seed, price_feed_ids_hash, AssetKind, AssetLookupTable, UnitasConfig};
+use crate::{ADMIN_CONFIG_SEED, ASSET_LOOKUP_TABLE_SEED, ASSET_LOOKUP_TABLE_VERSION, SOL_DECIMALS};
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(args: CreateAssetLookupTableArgs)]
 pub struct CreateAssetLookupTable<'info> {
@@ -15,15 +17,22 @@ pub struct CreateAssetLookupTable<'info> {
         init,
         payer = admin,
         space = AssetLookupTable::LEN,
-        seeds = [ASSET_LOOKUP_TABLE_SEED.as_bytes(), asset_mint.key().as_ref()],
+        seeds = [
+            ASSET_LOOKUP_TABLE_SEED.as_bytes(),
+            fund_seed(config.fund_id).as_ref(),
+            asset_mint.key().as_ref()
+        ],
         bump
     )]
     pub asset_lookup_table: AccountLoader<'info, AssetLookupTable>,
 
-    pub asset_mint: Account<'info, Mint>,
+    /// CHECK: A token mint, or the pseudo mint of a native asset kind; checked in
+    /// `process_create_asset_lookup_table`
+    pub asset_mint: UncheckedAccount<'info>,
 
     #[account(
-        seeds = [ADMIN_CONFIG_SEED.as_bytes()],
+        mut,
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
         bump,
         constraint = config.is_admin(&admin.key()) @ ErrorCode::InvalidAdmin
     )]
@@ -35,6 +44,8 @@ pub struct CreateAssetLookupTable<'info> {
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct CreateAssetLookupTableArgs {
     pub oracle_account: Pubkey,
+    /// Pyth feed id `oracle_account` must publish
+    pub oracle_feed_id: [u8; 32],
     pub decimals: u8,
 }
 
@@ -42,10 +53,39 @@ pub fn process_create_asset_lookup_table(
     ctx: Context<CreateAssetLookupTable>,
     args: CreateAssetLookupTableArgs,
 ) -> Result<()> {
+    let asset_mint = &ctx.accounts.asset_mint;
+    let asset_kind = AssetKind::from_asset_mint(asset_mint.key);
+    let decimals = match asset_kind {
+        AssetKind::SplToken => {
+            require_keys_eq!(*asset_mint.owner, token::ID, ErrorCode::InvalidAccount);
+            Mint::try_deserialize(&mut &asset_mint.try_borrow_data()?[..])?.decimals
+        }
+        AssetKind::NativeSol | AssetKind::StakeAccount => SOL_DECIMALS,
+    };
+    require_eq!(args.decimals, decimals, ErrorCode::InvalidDecimals);
+
     let mut asset_lookup_table = ctx.accounts.asset_lookup_table.load_init()?;
+    asset_lookup_table.config = ctx.accounts.config.key();
     asset_lookup_table.asset_mint = ctx.accounts.asset_mint.key();
     asset_lookup_table.oracle_account = args.oracle_account;
+    asset_lookup_table.price_feed_ids_hash = price_feed_ids_hash(&[args.oracle_feed_id]);
     asset_lookup_table.decimals = args.decimals;
     asset_lookup_table.token_account_owners_len = 0;
+    asset_lookup_table.version = ASSET_LOOKUP_TABLE_VERSION;
+    asset_lookup_table.asset_kind = asset_kind as u8;
+    drop(asset_lookup_table);
+
+    let config = &mut ctx.accounts.config;
+    config.asset_count = config
+        .asset_count
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit_event!(
+        ctx,
+        AssetLookupTableCreated {
+            lookup_table: ctx.accounts.asset_lookup_table.key(),
+        }
+    );
     Ok(())
 }
@@ -1,10 +1,13 @@
 use anchor_lang::prelude::*;
 
+use crate::error::ErrorCode;
 use crate::event::AdminConfigCreated;
-use crate::state::UnitasConfig;
-use crate::ADMIN_CONFIG_SEED;
+use crate::state::{fund_seed, ReservesUri, UnitasConfig};
+use crate::{ADMIN_CONFIG_SEED, CONFIG_VERSION, DEFAULT_MAX_AUM_AGE};
 
+#[event_cpi]
 #[derive(Accounts)]
+#[instruction(fund_id: u16)]
 pub struct InitAdminConfig<'info> {
     #[account(mut)]
     pub admin: Signer<'info>,
@@ -12,27 +15,81 @@ pub struct InitAdminConfig<'info> {
         init,
         payer = admin,
         space = UnitasConfig::LEN,
-        seeds = [ADMIN_CONFIG_SEED.as_bytes()],
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(fund_id).as_ref()],
         bump
     )]
     pub config: Account<'info, UnitasConfig>,
+    /// Config of fund 0, whose admin must sign to open any other fund; left out for fund 0
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(0).as_ref()],
+        bump
+    )]
+    pub fund_zero_config: Option<Account<'info, UnitasConfig>>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn process_init_admin_config(ctx: Context<InitAdminConfig>, usdu_config: Pubkey) -> Result<()> {
+pub fn process_init_admin_config(
+    ctx: Context<InitAdminConfig>,
+    fund_id: u16,
+    usdu_config: Pubkey,
+) -> Result<()> {
+    if fund_id != 0 {
+        let fund_zero_config = ctx
+            .accounts
+            .fund_zero_config
+            .as_ref()
+            .ok_or(ErrorCode::InvalidAdmin)?;
+        require!(
+            fund_zero_config.is_admin(&ctx.accounts.admin.key()),
+            ErrorCode::InvalidAdmin
+        );
+    }
+
     let config = &mut ctx.accounts.config;
     config.set_inner(UnitasConfig {
+        version: CONFIG_VERSION,
+        fund_id,
         admin: ctx.accounts.admin.key(),
         pending_admin: Pubkey::default(),
         aum_usd: 0,
         last_updated_timestamp: 0,
+        last_updated_slot: 0,
+        aum_sequence: 0,
+        aum_as_of: 0,
+        attested_aum_usd: 0,
+        usdu_supply: 0,
+        nav: 0,
+        reserves_root: [0; 32],
+        reserves_uri: ReservesUri::default(),
+        liabilities_root: [0; 32],
+        total_liabilities: 0,
+        liabilities_updated_timestamp: 0,
         usdu_config,
+        is_paused: false,
+        max_aum_age: DEFAULT_MAX_AUM_AGE,
+        onchain_aum_usd: 0,
+        onchain_aum_timestamp: 0,
+        asset_count: 0,
+        collateral_ratio_bps: 0,
+        collateral_surplus_usd: 0,
+        collateral_breaches: 0,
+        nav_peg_band_bps: 0,
+        nav_deviation_bps: 0,
+        nav_off_peg_since: 0,
+        has_price_feed: false,
+        bump: ctx.bumps.config,
+        paddings: [0; 2],
+        reserved: [0; 12],
     });
 
-    emit!(AdminConfigCreated {
-        admin: ctx.accounts.admin.key(),
-        config: ctx.accounts.config.key(),
-    });
+    emit_event!(
+        ctx,
+        AdminConfigCreated {
+            admin: ctx.accounts.admin.key(),
+            config: ctx.accounts.config.key(),
+            fund_id,
+        }
+    );
 
     Ok(())
 }
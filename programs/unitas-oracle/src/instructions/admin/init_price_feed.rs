@@ -0,0 +1,56 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::event::UsduPriceFeedCreated;
+use crate::state::{fund_seed, PriceFeedMessage, PriceUpdateV2, UnitasConfig, VerificationLevel};
+use crate::{ADMIN_CONFIG_SEED, USDU_PRICE_FEED_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct InitPriceFeed<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump,
+        constraint = config.is_admin(&admin.key()) @ ErrorCode::InvalidAdmin
+    )]
+    pub config: Account<'info, UnitasConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = PriceUpdateV2::LEN,
+        seeds = [USDU_PRICE_FEED_SEED.as_bytes(), config.key().as_ref()],
+        bump
+    )]
+    pub price_feed: Account<'info, PriceUpdateV2>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_init_price_feed(ctx: Context<InitPriceFeed>, feed_id: [u8; 32]) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.has_price_feed = true;
+    let mut price_feed = PriceUpdateV2 {
+        write_authority: config.key(),
+        verification_level: VerificationLevel::Full,
+        price_message: PriceFeedMessage {
+            feed_id,
+            ..Default::default()
+        },
+        posted_slot: 0,
+    };
+    if config.nav != 0 {
+        price_feed.publish_nav(config, &Clock::get()?)?;
+    }
+    ctx.accounts.price_feed.set_inner(price_feed);
+
+    emit_event!(
+        ctx,
+        UsduPriceFeedCreated {
+            price_feed: ctx.accounts.price_feed.key(),
+            feed_id,
+        }
+    );
+    Ok(())
+}
@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Transfer};
+
+use crate::error::ErrorCode;
+use crate::event::{AssetLookupTableMigrated, ConfigMigrated};
+use crate::state::{fund_seed, AssetLookupTable, UnitasConfig};
+use crate::{ADMIN_CONFIG_SEED, ASSET_LOOKUP_TABLE_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    /// CHECK: An older layout can't be loaded as `Account<UnitasConfig>`; the discriminator
+    /// and admin are checked in `process_migrate_config`. Older layouts only exist for fund 0.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(0).as_ref()],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct MigrateAssetLookupTable<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump,
+        constraint = config.is_admin(&admin.key()) @ ErrorCode::InvalidAdmin
+    )]
+    pub config: Account<'info, UnitasConfig>,
+    /// CHECK: An older layout can't be loaded as `AccountLoader<AssetLookupTable>`; the
+    /// discriminator and address are checked in `process_migrate_asset_lookup_table`
+    #[account(mut, owner = crate::ID)]
+    pub asset_lookup_table: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+    let info = ctx.accounts.config.to_account_info();
+    let mut config = UnitasConfig::migrate(&info.try_borrow_data()?)?;
+    config.bump = ctx.bumps.config;
+    require!(
+        config.is_admin(&ctx.accounts.admin.key()),
+        ErrorCode::InvalidAdmin
+    );
+
+    grow_account(
+        &info,
+        &ctx.accounts.admin,
+        &ctx.accounts.system_program,
+        UnitasConfig::LEN,
+    )?;
+    config.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+    emit_event!(
+        ctx,
+        ConfigMigrated {
+            config: info.key(),
+            version: config.version,
+        }
+    );
+    Ok(())
+}
+
+pub fn process_migrate_asset_lookup_table(ctx: Context<MigrateAssetLookupTable>) -> Result<()> {
+    let info = ctx.accounts.asset_lookup_table.to_account_info();
+    let config_key = ctx.accounts.config.key();
+    let asset_lookup_table = AssetLookupTable::migrate(&info.try_borrow_data()?, config_key)?;
+    // Older layouts don't record their config, so the table must sit at this fund's address
+    let (address, _) = Pubkey::find_program_address(
+        &[
+            ASSET_LOOKUP_TABLE_SEED.as_bytes(),
+            fund_seed(ctx.accounts.config.fund_id).as_ref(),
+            asset_lookup_table.asset_mint.as_ref(),
+        ],
+        &crate::ID,
+    );
+    require_keys_eq!(info.key(), address, ErrorCode::InvalidAccount);
+
+    grow_account(
+        &info,
+        &ctx.accounts.admin,
+        &ctx.accounts.system_program,
+        AssetLookupTable::LEN,
+    )?;
+    info.try_borrow_mut_data()?[8..].copy_from_slice(bytemuck::bytes_of(&asset_lookup_table));
+
+    // Tables created before versioning were never counted towards the valuation rounds
+    let config = &mut ctx.accounts.config;
+    config.asset_count = config
+        .asset_count
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    emit_event!(
+        ctx,
+        AssetLookupTableMigrated {
+            lookup_table: info.key(),
+            version: asset_lookup_table.version,
+        }
+    );
+    Ok(())
+}
+
+/// Resizes `account` to `new_len`, topping it up from `payer` to stay rent exempt.
+fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let shortfall = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if shortfall > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            shortfall,
+        )?;
+    }
+    account.resize(new_len)?;
+    Ok(())
+}
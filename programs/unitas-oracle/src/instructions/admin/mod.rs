@@ -1,11 +1,31 @@
+pub mod add_custodian;
 pub mod add_operator;
+pub mod close_asset_lookup_table;
 pub mod create_asset_lookup_table;
 pub mod init_config;
+pub mod init_price_feed;
+pub mod migrate;
+pub mod remove_custodian;
 pub mod remove_operator;
+pub mod set_asset_peg_band;
+pub mod set_asset_price_path;
+pub mod set_asset_pricing_mode;
+pub mod set_asset_stake_pool;
 pub mod transfer_admin;
+pub mod update_config;
 
+pub use add_custodian::*;
 pub use add_operator::*;
+pub use close_asset_lookup_table::*;
 pub use create_asset_lookup_table::*;
 pub use init_config::*;
+pub use init_price_feed::*;
+pub use migrate::*;
+pub use remove_custodian::*;
 pub use remove_operator::*;
+pub use set_asset_peg_band::*;
+pub use set_asset_price_path::*;
+pub use set_asset_pricing_mode::*;
+pub use set_asset_stake_pool::*;
 pub use transfer_admin::*;
+pub use update_config::*;
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::event::CustodianRemoved;
+use crate::state::{fund_seed, Custodian, PriceUpdateV2, UnitasConfig, ETH_ADDRESS_LEN};
+use crate::{ADMIN_CONFIG_SEED, CUSTODIAN_SEED, USDU_PRICE_FEED_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(eth_address: [u8; ETH_ADDRESS_LEN])]
+pub struct RemoveCustodian<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump,
+        constraint = config.is_admin(&admin.key()) @ ErrorCode::InvalidAdmin
+    )]
+    pub config: Account<'info, UnitasConfig>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [CUSTODIAN_SEED.as_bytes(), config.key().as_ref(), eth_address.as_ref()],
+        bump,
+        constraint = custodian.eth_address == eth_address @ ErrorCode::InvalidCustodian
+    )]
+    pub custodian: Account<'info, Custodian>,
+    /// Republished with the NAV; required once `init_price_feed` has run
+    #[account(
+        mut,
+        seeds = [USDU_PRICE_FEED_SEED.as_bytes(), config.key().as_ref()],
+        bump
+    )]
+    pub price_feed: Option<Account<'info, PriceUpdateV2>>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_remove_custodian(
+    ctx: Context<RemoveCustodian>,
+    eth_address: [u8; ETH_ADDRESS_LEN],
+) -> Result<()> {
+    let clock = Clock::get()?;
+    // The custodian's last statement no longer backs the AUM once it is removed.
+    let config = &mut ctx.accounts.config;
+    config.attested_aum_usd = config
+        .attested_aum_usd
+        .checked_sub(ctx.accounts.custodian.attested_balance_usd)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let refresh = config.refresh_nav(clock.unix_timestamp)?;
+    PriceUpdateV2::republish_nav(ctx.accounts.price_feed.as_deref_mut(), config, &clock)?;
+
+    emit_event!(
+        ctx,
+        CustodianRemoved {
+            custodian: ctx.accounts.custodian.key(),
+            eth_address,
+        }
+    );
+    emit_nav_refresh!(ctx, refresh);
+    Ok(())
+}
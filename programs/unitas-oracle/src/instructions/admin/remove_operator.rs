@@ -2,9 +2,10 @@ use anchor_lang::prelude::*;
 
 use crate::error::ErrorCode;
 use crate::event::OperatorRemoved;
-use crate::state::{Operator, UnitasConfig};
+use crate::state::{fund_seed, Operator, UnitasConfig};
 use crate::{ADMIN_CONFIG_SEED, OPERATOR_SEED};
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(user: Pubkey)]
 pub struct RemoveOperator<'info> {
@@ -12,7 +13,7 @@ pub struct RemoveOperator<'info> {
     pub admin: Signer<'info>,
     #[account(
         mut,
-        seeds = [ADMIN_CONFIG_SEED.as_bytes()],
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
         bump,
         constraint = config.is_admin(&admin.key()) @ ErrorCode::InvalidAdmin
     )]
@@ -20,7 +21,7 @@ pub struct RemoveOperator<'info> {
     #[account(
         mut,
         close = admin,
-        seeds = [OPERATOR_SEED.as_bytes(), user.as_ref()],
+        seeds = [OPERATOR_SEED.as_bytes(), fund_seed(config.fund_id).as_ref(), user.as_ref()],
         bump,
         constraint = operator.user == user @ ErrorCode::InvalidOperator
     )]
@@ -37,9 +38,12 @@ pub fn process_remove_operator(ctx: Context<RemoveOperator>, user: Pubkey) -> Re
         ctx.accounts.operator.key()
     );
 
-    emit!(OperatorRemoved {
-        user,
-        operator: ctx.accounts.operator.key(),
-    });
+    emit_event!(
+        ctx,
+        OperatorRemoved {
+            user,
+            operator: ctx.accounts.operator.key(),
+        }
+    );
     Ok(())
 }
@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::event::AssetPegBandSet;
+use crate::state::{fund_seed, AssetLookupTable, UnitasConfig};
+use crate::{ADMIN_CONFIG_SEED, ASSET_LOOKUP_TABLE_SEED, BPS_DENOMINATOR};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetAssetPegBand<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump,
+        constraint = config.is_admin(&admin.key()) @ ErrorCode::InvalidAdmin
+    )]
+    pub config: Account<'info, UnitasConfig>,
+    #[account(
+        mut,
+        seeds = [
+            ASSET_LOOKUP_TABLE_SEED.as_bytes(),
+            fund_seed(config.fund_id).as_ref(),
+            asset_lookup_table.load()?.asset_mint.as_ref()
+        ],
+        bump
+    )]
+    pub asset_lookup_table: AccountLoader<'info, AssetLookupTable>,
+}
+
+pub fn process_set_asset_peg_band(ctx: Context<SetAssetPegBand>, peg_band_bps: u16) -> Result<()> {
+    require_gte!(
+        BPS_DENOMINATOR,
+        peg_band_bps as u64,
+        ErrorCode::InvalidPegBand
+    );
+
+    let mut asset_lookup_table = ctx.accounts.asset_lookup_table.load_mut()?;
+    asset_lookup_table.peg_band_bps = peg_band_bps;
+    // The next valuation reports the peg status against the new band
+    asset_lookup_table.is_depegged = 0;
+    drop(asset_lookup_table);
+
+    emit_event!(
+        ctx,
+        AssetPegBandSet {
+            lookup_table: ctx.accounts.asset_lookup_table.key(),
+            peg_band_bps,
+        }
+    );
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::event::AssetPricePathSet;
+use crate::state::{fund_seed, AssetLookupTable, PriceLeg, UnitasConfig};
+use crate::{ADMIN_CONFIG_SEED, ASSET_LOOKUP_TABLE_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetAssetPricePath<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump,
+        constraint = config.is_admin(&admin.key()) @ ErrorCode::InvalidAdmin
+    )]
+    pub config: Account<'info, UnitasConfig>,
+    #[account(
+        mut,
+        seeds = [
+            ASSET_LOOKUP_TABLE_SEED.as_bytes(),
+            fund_seed(config.fund_id).as_ref(),
+            asset_lookup_table.load()?.asset_mint.as_ref()
+        ],
+        bump
+    )]
+    pub asset_lookup_table: AccountLoader<'info, AssetLookupTable>,
+}
+
+pub fn process_set_asset_price_path(
+    ctx: Context<SetAssetPricePath>,
+    legs: Vec<PriceLeg>,
+    feed_ids: Vec<[u8; 32]>,
+) -> Result<()> {
+    ctx.accounts
+        .asset_lookup_table
+        .load_mut()?
+        .set_price_path(&legs, &feed_ids)?;
+
+    emit_event!(
+        ctx,
+        AssetPricePathSet {
+            lookup_table: ctx.accounts.asset_lookup_table.key(),
+            legs,
+            feed_ids,
+        }
+    );
+    Ok(())
+}
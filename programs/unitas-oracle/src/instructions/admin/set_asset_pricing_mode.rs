@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::event::AssetPricingModeSet;
+use crate::state::{fund_seed, AssetLookupTable, PricingMode, UnitasConfig};
+use crate::{ADMIN_CONFIG_SEED, ASSET_LOOKUP_TABLE_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetAssetPricingMode<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump,
+        constraint = config.is_admin(&admin.key()) @ ErrorCode::InvalidAdmin
+    )]
+    pub config: Account<'info, UnitasConfig>,
+    #[account(
+        mut,
+        seeds = [
+            ASSET_LOOKUP_TABLE_SEED.as_bytes(),
+            fund_seed(config.fund_id).as_ref(),
+            asset_lookup_table.load()?.asset_mint.as_ref()
+        ],
+        bump
+    )]
+    pub asset_lookup_table: AccountLoader<'info, AssetLookupTable>,
+}
+
+pub fn process_set_asset_pricing_mode(
+    ctx: Context<SetAssetPricingMode>,
+    pricing_mode: PricingMode,
+) -> Result<()> {
+    ctx.accounts.asset_lookup_table.load_mut()?.pricing_mode = pricing_mode as u8;
+
+    emit_event!(
+        ctx,
+        AssetPricingModeSet {
+            lookup_table: ctx.accounts.asset_lookup_table.key(),
+            pricing_mode,
+        }
+    );
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::event::AssetStakePoolSet;
+use crate::state::{fund_seed, AssetKind, AssetLookupTable, StakePoolRate, UnitasConfig};
+use crate::{ADMIN_CONFIG_SEED, ASSET_LOOKUP_TABLE_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SetAssetStakePool<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump,
+        constraint = config.is_admin(&admin.key()) @ ErrorCode::InvalidAdmin
+    )]
+    pub config: Account<'info, UnitasConfig>,
+    #[account(
+        mut,
+        seeds = [
+            ASSET_LOOKUP_TABLE_SEED.as_bytes(),
+            fund_seed(config.fund_id).as_ref(),
+            asset_lookup_table.load()?.asset_mint.as_ref()
+        ],
+        bump
+    )]
+    pub asset_lookup_table: AccountLoader<'info, AssetLookupTable>,
+    /// CHECK: SPL stake pool of the table's mint, validated by `StakePoolRate::load`; omit it
+    /// to price the table with its oracle alone again
+    pub stake_pool: Option<UncheckedAccount<'info>>,
+}
+
+pub fn process_set_asset_stake_pool(ctx: Context<SetAssetStakePool>) -> Result<()> {
+    let mut asset_lookup_table = ctx.accounts.asset_lookup_table.load_mut()?;
+    let stake_pool = match &ctx.accounts.stake_pool {
+        Some(stake_pool) => {
+            require!(
+                asset_lookup_table.asset_kind()? == AssetKind::SplToken,
+                ErrorCode::InvalidStakePool
+            );
+            let rate = StakePoolRate::load(stake_pool)?;
+            require_keys_eq!(
+                rate.pool_mint,
+                asset_lookup_table.asset_mint,
+                ErrorCode::InvalidStakePool
+            );
+            stake_pool.key()
+        }
+        None => Pubkey::default(),
+    };
+    asset_lookup_table.stake_pool = stake_pool;
+    drop(asset_lookup_table);
+
+    emit_event!(
+        ctx,
+        AssetStakePoolSet {
+            lookup_table: ctx.accounts.asset_lookup_table.key(),
+            stake_pool,
+        }
+    );
+    Ok(())
+}
@@ -2,9 +2,10 @@ use anchor_lang::prelude::*;
 
 use crate::error::ErrorCode;
 use crate::event::{AdminTransferCompleted, AdminTransferProposed};
-use crate::state::UnitasConfig;
+use crate::state::{fund_seed, UnitasConfig};
 use crate::ADMIN_CONFIG_SEED;
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct ProposeNewAdmin<'info> {
     #[account(mut)]
@@ -15,7 +16,7 @@ pub struct ProposeNewAdmin<'info> {
 
     #[account(
         mut,
-        seeds = [ADMIN_CONFIG_SEED.as_bytes()],
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
         bump,
         constraint = config.admin == current_admin.key() @ ErrorCode::OnlyAdminCanProposeNewAdmin,
     )]
@@ -24,13 +25,14 @@ pub struct ProposeNewAdmin<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct AcceptAdminTransfer<'info> {
     #[account(mut)]
     pub new_admin: Signer<'info>,
     #[account(
         mut,
-        seeds = [ADMIN_CONFIG_SEED.as_bytes()],
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
         bump,
         constraint = config.pending_admin == new_admin.key() @ ErrorCode::OnlyProposedAdminCanAccept,
     )]
@@ -53,10 +55,13 @@ pub fn process_propose_new_admin(ctx: Context<ProposeNewAdmin>) -> Result<()> {
 
     config.pending_admin = ctx.accounts.proposed_admin.key();
 
-    emit!(AdminTransferProposed {
-        current_admin: ctx.accounts.current_admin.key(),
-        proposed_admin: ctx.accounts.proposed_admin.key(),
-    });
+    emit_event!(
+        ctx,
+        AdminTransferProposed {
+            current_admin: ctx.accounts.current_admin.key(),
+            proposed_admin: ctx.accounts.proposed_admin.key(),
+        }
+    );
 
     Ok(())
 }
@@ -69,10 +74,13 @@ pub fn process_accept_admin_transfer(ctx: Context<AcceptAdminTransfer>) -> Resul
     config.admin = ctx.accounts.new_admin.key();
     config.pending_admin = Pubkey::default();
 
-    emit!(AdminTransferCompleted {
-        previous_admin,
-        new_admin: ctx.accounts.new_admin.key(),
-    });
+    emit_event!(
+        ctx,
+        AdminTransferCompleted {
+            previous_admin,
+            new_admin: ctx.accounts.new_admin.key(),
+        }
+    );
 
     Ok(())
 }
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::event::{MaxAumAgeUpdated, NavPegBandUpdated, PausedUpdated};
+use crate::state::{fund_seed, UnitasConfig};
+use crate::{ADMIN_CONFIG_SEED, BPS_DENOMINATOR};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump,
+        constraint = config.is_admin(&admin.key()) @ ErrorCode::InvalidAdmin
+    )]
+    pub config: Account<'info, UnitasConfig>,
+}
+
+pub fn process_set_paused(ctx: Context<UpdateConfig>, paused: bool) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.is_paused = paused;
+
+    emit_event!(
+        ctx,
+        PausedUpdated {
+            paused,
+            config: config.key(),
+        }
+    );
+    Ok(())
+}
+
+pub fn process_set_max_aum_age(ctx: Context<UpdateConfig>, max_aum_age: i64) -> Result<()> {
+    require!(max_aum_age > 0, ErrorCode::InvalidMaxAumAge);
+    let config = &mut ctx.accounts.config;
+    config.max_aum_age = max_aum_age;
+
+    emit_event!(
+        ctx,
+        MaxAumAgeUpdated {
+            max_aum_age,
+            config: config.key(),
+        }
+    );
+    Ok(())
+}
+
+pub fn process_set_nav_peg_band(ctx: Context<UpdateConfig>, nav_peg_band_bps: u32) -> Result<()> {
+    require_gte!(
+        BPS_DENOMINATOR,
+        nav_peg_band_bps as u64,
+        ErrorCode::InvalidPegBand
+    );
+    let config = &mut ctx.accounts.config;
+    config.nav_peg_band_bps = nav_peg_band_bps;
+    // The next published NAV is checked against the new band from scratch
+    config.nav_off_peg_since = 0;
+
+    emit_event!(
+        ctx,
+        NavPegBandUpdated {
+            nav_peg_band_bps,
+            config: config.key(),
+        }
+    );
+    Ok(())
+}
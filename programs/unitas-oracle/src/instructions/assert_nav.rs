@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::state::{fund_seed, NavStatus, UnitasConfig};
+use crate::ADMIN_CONFIG_SEED;
+
+/// Guards meant to be prepended to other transactions: they only read the config and
+/// abort the whole transaction when the condition does not hold.
+#[derive(Accounts)]
+pub struct AssertNav<'info> {
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump
+    )]
+    pub config: Account<'info, UnitasConfig>,
+}
+
+/// Fails unless the NAV is published, not paused, and its inputs were observed no longer ago
+/// than both `max_age` and the configured maximum AUM age, see `UnitasConfig::nav_as_of`.
+pub fn process_assert_nav_fresh(ctx: Context<AssertNav>, max_age: i64) -> Result<()> {
+    let config = &ctx.accounts.config;
+    let now = Clock::get()?.unix_timestamp;
+    require_nav_available(config, now)?;
+    require!(
+        now.saturating_sub(config.nav_as_of()) <= max_age,
+        ErrorCode::StaleNav
+    );
+    Ok(())
+}
+
+/// Fails unless total AUM covers at least `min_bps` of the USDU supply.
+pub fn process_assert_collateral_ratio(ctx: Context<AssertNav>, min_bps: u64) -> Result<()> {
+    let config = &ctx.accounts.config;
+    require_nav_available(config, Clock::get()?.unix_timestamp)?;
+    require_gte!(
+        config.collateral_ratio_bps()?,
+        min_bps,
+        ErrorCode::CollateralRatioBelowMinimum
+    );
+    Ok(())
+}
+
+fn require_nav_available(config: &UnitasConfig, now: i64) -> Result<()> {
+    match config.nav_status(now) {
+        NavStatus::Active => Ok(()),
+        NavStatus::Stale => err!(ErrorCode::StaleNav),
+        NavStatus::Paused => err!(ErrorCode::OraclePaused),
+        NavStatus::Unavailable => err!(ErrorCode::NavUnavailable),
+    }
+}
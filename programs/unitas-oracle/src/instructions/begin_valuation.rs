@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::access_control::require_admin_or_operator;
+use crate::event::ValuationRoundStarted;
+use crate::state::{fund_seed, UnitasConfig, ValuationRound};
+use crate::{ADMIN_CONFIG_SEED, VALUATION_ROUND_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BeginValuation<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump
+    )]
+    pub config: Account<'info, UnitasConfig>,
+    /// CHECK: Validated by `require_admin_or_operator` unless the user is the admin
+    pub operator: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = ValuationRound::LEN,
+        seeds = [VALUATION_ROUND_SEED.as_bytes(), config.key().as_ref()],
+        bump
+    )]
+    pub valuation_round: Account<'info, ValuationRound>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Opens a valuation round over every asset table currently registered under the config.
+pub fn process_begin_valuation(ctx: Context<BeginValuation>) -> Result<()> {
+    require_admin_or_operator(
+        &ctx.accounts.config,
+        &ctx.accounts.user.key(),
+        &ctx.accounts.operator,
+    )?;
+
+    let now = Clock::get()?.unix_timestamp;
+    let config = &ctx.accounts.config;
+    let valuation_round = &mut ctx.accounts.valuation_round;
+    valuation_round.begin(config.key(), config.asset_count, now)?;
+
+    emit_event!(
+        ctx,
+        ValuationRoundStarted {
+            config: config.key(),
+            round: valuation_round.round,
+            asset_count: valuation_round.asset_count,
+            started_at: valuation_round.started_at,
+        }
+    );
+    Ok(())
+}
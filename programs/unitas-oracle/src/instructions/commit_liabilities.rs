@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::access_control::require_admin_or_operator;
+use crate::event::LiabilitiesCommitted;
+use crate::state::{fund_seed, UnitasConfig};
+use crate::ADMIN_CONFIG_SEED;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CommitLiabilities<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump
+    )]
+    pub config: Account<'info, UnitasConfig>,
+    /// CHECK: Validated by `require_admin_or_operator` unless the user is the admin
+    pub operator: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CommitLiabilitiesArgs {
+    /// Root hash of the Merkle-sum tree over USDU holder balances
+    pub liabilities_root: [u8; 32],
+    /// Sum committed at the root of that tree
+    pub total_liabilities: u128,
+}
+
+pub fn process_commit_liabilities(
+    ctx: Context<CommitLiabilities>,
+    args: CommitLiabilitiesArgs,
+) -> Result<()> {
+    require_admin_or_operator(
+        &ctx.accounts.config,
+        &ctx.accounts.user.key(),
+        &ctx.accounts.operator,
+    )?;
+
+    let timestamp = Clock::get()?.unix_timestamp;
+    let config = &mut ctx.accounts.config;
+    config.liabilities_root = args.liabilities_root;
+    config.total_liabilities = args.total_liabilities;
+    config.liabilities_updated_timestamp = timestamp;
+    emit_event!(
+        ctx,
+        LiabilitiesCommitted {
+            liabilities_root: args.liabilities_root,
+            total_liabilities: args.total_liabilities,
+            timestamp,
+            config: config.key(),
+        }
+    );
+    Ok(())
+}
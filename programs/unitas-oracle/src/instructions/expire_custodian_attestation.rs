@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::event::CustodianAttestationExpired;
+use crate::state::{fund_seed, Custodian, PriceUpdateV2, UnitasConfig};
+use crate::{ADMIN_CONFIG_SEED, CUSTODIAN_SEED, USDU_PRICE_FEED_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExpireCustodianAttestation<'info> {
+    #[account(
+        mut,
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump
+    )]
+    pub config: Account<'info, UnitasConfig>,
+    #[account(
+        mut,
+        seeds = [
+            CUSTODIAN_SEED.as_bytes(),
+            config.key().as_ref(),
+            custodian.eth_address.as_ref()
+        ],
+        bump
+    )]
+    pub custodian: Account<'info, Custodian>,
+    /// Republished with the NAV; required once `init_price_feed` has run
+    #[account(
+        mut,
+        seeds = [USDU_PRICE_FEED_SEED.as_bytes(), config.key().as_ref()],
+        bump
+    )]
+    pub price_feed: Option<Account<'info, PriceUpdateV2>>,
+}
+
+/// Anyone may drop a custodian balance whose statement is older than `max_aum_age` from the
+/// attested AUM, so a custodian that stops attesting can't keep backing the NAV.
+pub fn process_expire_custodian_attestation(
+    ctx: Context<ExpireCustodianAttestation>,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let custodian = &mut ctx.accounts.custodian;
+    let config = &mut ctx.accounts.config;
+    require!(
+        custodian.is_expired(clock.unix_timestamp, config.max_aum_age),
+        ErrorCode::AttestationNotExpired
+    );
+
+    let expired_balance_usd = std::mem::take(&mut custodian.attested_balance_usd);
+    // The timestamp is kept so the expired statement can't be submitted again
+    config.attested_aum_usd = config
+        .attested_aum_usd
+        .checked_sub(expired_balance_usd)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let refresh = config.refresh_nav(clock.unix_timestamp)?;
+    PriceUpdateV2::republish_nav(ctx.accounts.price_feed.as_deref_mut(), config, &clock)?;
+
+    emit_event!(
+        ctx,
+        CustodianAttestationExpired {
+            custodian: custodian.key(),
+            eth_address: custodian.eth_address,
+            balance_usd: expired_balance_usd,
+            timestamp: custodian.attested_timestamp,
+            attested_aum_usd: config.attested_aum_usd,
+            nav: config.nav,
+        }
+    );
+    emit_nav_refresh!(ctx, refresh);
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::event::ValuationRoundFinalized;
+use crate::state::{fund_seed, PriceUpdateV2, UnitasConfig, UsduConfig, ValuationRound};
+use crate::{ADMIN_CONFIG_SEED, USDU_PRICE_FEED_SEED, VALUATION_ROUND_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FinalizeValuation<'info> {
+    #[account(
+        mut,
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump
+    )]
+    pub config: Account<'info, UnitasConfig>,
+    #[account(
+        mut,
+        seeds = [VALUATION_ROUND_SEED.as_bytes(), config.key().as_ref()],
+        bump
+    )]
+    pub valuation_round: Account<'info, ValuationRound>,
+    /// CHECK: USDU program config, read for the token supply
+    #[account(address = config.usdu_config @ ErrorCode::InvalidUsduConfig)]
+    pub usdu_config: UncheckedAccount<'info>,
+    /// Republished with the NAV; required once `init_price_feed` has run
+    #[account(
+        mut,
+        seeds = [USDU_PRICE_FEED_SEED.as_bytes(), config.key().as_ref()],
+        bump
+    )]
+    pub price_feed: Option<Account<'info, PriceUpdateV2>>,
+}
+
+/// Publishes the round's total as the on-chain AUM once every registered asset is in.
+pub fn process_finalize_valuation(ctx: Context<FinalizeValuation>) -> Result<()> {
+    let clock = Clock::get()?;
+    let config = &mut ctx.accounts.config;
+    let valuation_round = &mut ctx.accounts.valuation_round;
+    valuation_round.finalize(config.asset_count, clock.unix_timestamp)?;
+
+    config.onchain_aum_usd = valuation_round.total_usd_value;
+    config.onchain_aum_timestamp = valuation_round.started_at;
+    config.usdu_supply = UsduConfig::read_total_supply(&ctx.accounts.usdu_config)?;
+    let refresh = config.refresh_nav(clock.unix_timestamp)?;
+    PriceUpdateV2::republish_nav(ctx.accounts.price_feed.as_deref_mut(), config, &clock)?;
+
+    emit_event!(
+        ctx,
+        ValuationRoundFinalized {
+            config: config.key(),
+            round: valuation_round.round,
+            onchain_aum_usd: config.onchain_aum_usd,
+            onchain_aum_timestamp: config.onchain_aum_timestamp,
+            usdu_supply: config.usdu_supply,
+            nav: config.nav,
+        }
+    );
+    emit_nav_refresh!(ctx, refresh);
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{fund_seed, NavStatus, UnitasConfig};
+use crate::{ADMIN_CONFIG_SEED, NAV_DATA_VERSION};
+
+#[derive(Accounts)]
+pub struct GetNav<'info> {
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump
+    )]
+    pub config: Account<'info, UnitasConfig>,
+}
+
+/// Snapshot returned by `get_nav`. Fields are only ever appended, behind a new `version`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct NavData {
+    pub version: u8,
+    pub aum_usd: u128,
+    pub nav: u64,
+    pub supply: u128,
+    /// When the oldest input of the NAV was observed, see `UnitasConfig::nav_as_of`
+    pub timestamp: i64,
+    pub slot: u64,
+    pub status: NavStatus,
+}
+
+pub fn process_get_nav(ctx: Context<GetNav>) -> Result<NavData> {
+    let config = &ctx.accounts.config;
+    let now = Clock::get()?.unix_timestamp;
+    Ok(NavData {
+        version: NAV_DATA_VERSION,
+        aum_usd: config.total_aum_usd()?,
+        nav: config.nav,
+        supply: config.usdu_supply,
+        timestamp: config.nav_as_of(),
+        slot: config.last_updated_slot,
+        status: config.nav_status(now),
+    })
+}
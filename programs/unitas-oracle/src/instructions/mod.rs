@@ -1,9 +1,33 @@
+pub mod accumulate_asset;
 pub mod add_account;
+pub mod add_accounts;
 pub mod admin;
+pub mod assert_nav;
+pub mod begin_valuation;
+pub mod commit_liabilities;
+pub mod expire_custodian_attestation;
+pub mod finalize_valuation;
+pub mod get_nav;
+pub mod record_asset_valuation;
 pub mod remove_account;
+pub mod remove_accounts;
+pub mod set_account_label;
+pub mod submit_custodian_attestation;
 pub mod update_aum_usd;
 
+pub use accumulate_asset::*;
 pub use add_account::*;
+pub use add_accounts::*;
 pub use admin::*;
+pub use assert_nav::*;
+pub use begin_valuation::*;
+pub use commit_liabilities::*;
+pub use expire_custodian_attestation::*;
+pub use finalize_valuation::*;
+pub use get_nav::*;
+pub use record_asset_valuation::*;
 pub use remove_account::*;
+pub use remove_accounts::*;
+pub use set_account_label::*;
+pub use submit_custodian_attestation::*;
 pub use update_aum_usd::*;
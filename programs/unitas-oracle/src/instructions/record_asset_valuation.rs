@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+use crate::access_control::require_admin_or_operator;
+use crate::error::ErrorCode;
+use crate::event::AssetValuationRecorded;
+use crate::state::{
+    fund_seed, usd_value, AssetLookupTable, PricingMode, UnitasConfig, ValuationSource,
+};
+use crate::{ADMIN_CONFIG_SEED, ASSET_LOOKUP_TABLE_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RecordAssetValuation<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            ASSET_LOOKUP_TABLE_SEED.as_bytes(),
+            fund_seed(config.fund_id).as_ref(),
+            asset_lookup_table.load()?.asset_mint.as_ref()
+        ],
+        bump
+    )]
+    pub asset_lookup_table: AccountLoader<'info, AssetLookupTable>,
+
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump
+    )]
+    pub config: Account<'info, UnitasConfig>,
+
+    /// CHECK: Validated by `require_admin_or_operator` unless the user is the admin
+    pub operator: UncheckedAccount<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RecordAssetValuationArgs {
+    /// Price of one whole token, scaled by `10^price_exponent`. Like on chain, a pegged
+    /// stablecoin within its band is valued at exactly $1 instead
+    pub price: i64,
+    pub price_exponent: i32,
+    /// Must be the table's pricing mode
+    pub pricing_mode: PricingMode,
+    /// Sum of the tracked token account balances, in base units; valued on chain at `price`
+    pub total_amount: u64,
+    /// When the valuation was taken
+    pub valued_at: i64,
+}
+
+/// Caches an off-chain valuation of the asset on its lookup table, marked as recorded by the
+/// operator so it can't pass for an on-chain one.
+pub fn process_record_asset_valuation(
+    ctx: Context<RecordAssetValuation>,
+    args: RecordAssetValuationArgs,
+) -> Result<()> {
+    require_admin_or_operator(
+        &ctx.accounts.config,
+        &ctx.accounts.user.key(),
+        &ctx.accounts.operator,
+    )?;
+    let now = Clock::get()?.unix_timestamp;
+    require!(args.valued_at <= now, ErrorCode::InvalidTimestamp);
+
+    let mut asset_lookup_table = ctx.accounts.asset_lookup_table.load_mut()?;
+    require!(
+        args.pricing_mode == asset_lookup_table.pricing_mode()?,
+        ErrorCode::PricingModeMismatch
+    );
+    let (asset_price, peg_change) =
+        asset_lookup_table.update_peg(args.price, args.price_exponent)?;
+    let value = usd_value(
+        args.total_amount,
+        asset_lookup_table.decimals,
+        asset_price,
+        args.price_exponent,
+    )?;
+    asset_lookup_table.record_valuation(
+        asset_price,
+        args.price_exponent,
+        args.total_amount,
+        value,
+        args.valued_at,
+        ValuationSource::Operator,
+    )?;
+    let asset_mint = asset_lookup_table.asset_mint;
+    let peg_band_bps = asset_lookup_table.peg_band_bps;
+    drop(asset_lookup_table);
+
+    emit_event!(
+        ctx,
+        AssetValuationRecorded {
+            lookup_table: ctx.accounts.asset_lookup_table.key(),
+            asset_mint,
+            price: asset_price,
+            price_exponent: args.price_exponent,
+            pricing_mode: args.pricing_mode,
+            total_amount: args.total_amount,
+            usd_value: value,
+            valued_at: args.valued_at,
+            source: ValuationSource::Operator,
+        }
+    );
+    emit_peg_change!(
+        ctx,
+        peg_change,
+        asset_mint,
+        args.price,
+        args.price_exponent,
+        peg_band_bps
+    );
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::access_control::require_admin_or_operator;
+use crate::event::AccountLabelSet;
+use crate::state::{fund_seed, AccountCategory, AccountLabel, UnitasConfig};
+use crate::{ACCOUNT_LABEL_SEED, ADMIN_CONFIG_SEED};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(account: Pubkey)]
+pub struct SetAccountLabel<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump
+    )]
+    pub config: Account<'info, UnitasConfig>,
+
+    /// CHECK: Validated by `require_admin_or_operator` unless the user is the admin
+    pub operator: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = AccountLabel::LEN,
+        seeds = [ACCOUNT_LABEL_SEED.as_bytes(), config.key().as_ref(), account.as_ref()],
+        bump
+    )]
+    pub account_label: Account<'info, AccountLabel>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_set_account_label(
+    ctx: Context<SetAccountLabel>,
+    account: Pubkey,
+    label: String,
+    category: AccountCategory,
+) -> Result<()> {
+    require_admin_or_operator(
+        &ctx.accounts.config,
+        &ctx.accounts.user.key(),
+        &ctx.accounts.operator,
+    )?;
+
+    let account_label = &mut ctx.accounts.account_label;
+    account_label.config = ctx.accounts.config.key();
+    account_label.account = account;
+    account_label.set_label(&label)?;
+    account_label.category = category;
+
+    emit_event!(
+        ctx,
+        AccountLabelSet {
+            config: ctx.accounts.config.key(),
+            account,
+            label,
+            category,
+        }
+    );
+    Ok(())
+}
@@ -0,0 +1,304 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::secp256k1_program;
+use anchor_lang::solana_program::sysvar::instructions::{
+    self as instructions_sysvar, load_current_index_checked, load_instruction_at_checked,
+};
+
+use crate::error::ErrorCode;
+use crate::event::CustodianBalanceAttested;
+use crate::state::{fund_seed, Custodian, PriceUpdateV2, UnitasConfig, ETH_ADDRESS_LEN};
+use crate::{
+    ADMIN_CONFIG_SEED, CUSTODIAN_SEED, MAX_CUSTODIAN_ATTESTATION_AGE, USDU_PRICE_FEED_SEED,
+};
+
+// num_signatures (1) + SecpSignatureOffsets (11)
+const SECP256K1_HEADER_LEN: usize = 1 + 11;
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SubmitCustodianAttestation<'info> {
+    #[account(
+        mut,
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
+        bump
+    )]
+    pub config: Account<'info, UnitasConfig>,
+    #[account(
+        mut,
+        seeds = [
+            CUSTODIAN_SEED.as_bytes(),
+            config.key().as_ref(),
+            custodian.eth_address.as_ref()
+        ],
+        bump
+    )]
+    pub custodian: Account<'info, Custodian>,
+    /// Republished with the NAV; required once `init_price_feed` has run
+    #[account(
+        mut,
+        seeds = [USDU_PRICE_FEED_SEED.as_bytes(), config.key().as_ref()],
+        bump
+    )]
+    pub price_feed: Option<Account<'info, PriceUpdateV2>>,
+    /// CHECK: This is the instructions sysvar, checked by address
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CustodianAttestationArgs {
+    pub balance_usd: u128,
+    pub timestamp: i64,
+}
+
+/// Anyone may relay a statement: the custodian's secp256k1 signature, verified by the
+/// instruction placed right before this one, is what authorises the update.
+pub fn process_submit_custodian_attestation(
+    ctx: Context<SubmitCustodianAttestation>,
+    args: CustodianAttestationArgs,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let now = clock.unix_timestamp;
+    let custodian = &mut ctx.accounts.custodian;
+    require!(
+        args.timestamp > custodian.attested_timestamp && args.timestamp <= now,
+        ErrorCode::InvalidTimestamp
+    );
+    require!(
+        now - args.timestamp <= MAX_CUSTODIAN_ATTESTATION_AGE,
+        ErrorCode::StaleAttestation
+    );
+
+    let message = Custodian::attestation_message(
+        &ctx.accounts.config.key(),
+        &custodian.eth_address,
+        args.balance_usd,
+        args.timestamp,
+    );
+    verify_secp256k1_instruction(
+        &ctx.accounts.instructions_sysvar.to_account_info(),
+        &custodian.eth_address,
+        &message,
+    )?;
+
+    let config = &mut ctx.accounts.config;
+    config.attested_aum_usd = config
+        .attested_aum_usd
+        .checked_sub(custodian.attested_balance_usd)
+        .and_then(|aum| aum.checked_add(args.balance_usd))
+        .ok_or(ErrorCode::MathOverflow)?;
+    let refresh = config.refresh_nav(clock.unix_timestamp)?;
+    PriceUpdateV2::republish_nav(ctx.accounts.price_feed.as_deref_mut(), config, &clock)?;
+    custodian.attested_balance_usd = args.balance_usd;
+    custodian.attested_timestamp = args.timestamp;
+
+    emit_event!(
+        ctx,
+        CustodianBalanceAttested {
+            custodian: custodian.key(),
+            eth_address: custodian.eth_address,
+            balance_usd: args.balance_usd,
+            timestamp: args.timestamp,
+            attested_aum_usd: config.attested_aum_usd,
+            nav: config.nav,
+        }
+    );
+    emit_nav_refresh!(ctx, refresh);
+    Ok(())
+}
+
+/// Checks that the previous instruction is a secp256k1 precompile call over exactly
+/// `message`, recovered to `eth_address`, with all of its data held inline.
+fn verify_secp256k1_instruction(
+    instructions_sysvar: &AccountInfo,
+    eth_address: &[u8; ETH_ADDRESS_LEN],
+    message: &[u8],
+) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    require!(current_index > 0, ErrorCode::MissingSecp256k1Instruction);
+    let secp_index = current_index - 1;
+    let ix = load_instruction_at_checked(secp_index as usize, instructions_sysvar)?;
+    require_keys_eq!(
+        ix.program_id,
+        secp256k1_program::ID,
+        ErrorCode::MissingSecp256k1Instruction
+    );
+
+    let data = &ix.data;
+    require!(
+        data.len() >= SECP256K1_HEADER_LEN && data[0] == 1,
+        ErrorCode::InvalidSecp256k1Instruction
+    );
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]) as usize;
+    let signature_instruction_index = data[3];
+    let eth_address_offset = read_u16(4);
+    let eth_address_instruction_index = data[6];
+    let message_data_offset = read_u16(7);
+    let message_data_size = read_u16(9);
+    let message_instruction_index = data[11];
+
+    // The precompile may read from other instructions; only accept data it read from itself.
+    require!(
+        [
+            signature_instruction_index,
+            eth_address_instruction_index,
+            message_instruction_index,
+        ]
+        .iter()
+        .all(|index| *index as u16 == secp_index),
+        ErrorCode::InvalidSecp256k1Instruction
+    );
+
+    let signed_eth_address = data
+        .get(eth_address_offset..eth_address_offset + ETH_ADDRESS_LEN)
+        .ok_or(ErrorCode::InvalidSecp256k1Instruction)?;
+    let signed_message = data
+        .get(message_data_offset..message_data_offset + message_data_size)
+        .ok_or(ErrorCode::InvalidSecp256k1Instruction)?;
+    require!(
+        signed_eth_address == eth_address && signed_message == message,
+        ErrorCode::AttestationMismatch
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[allow(deprecated)]
+    use anchor_lang::solana_program::sysvar::instructions::{
+        construct_instructions_data, BorrowedInstruction,
+    };
+
+    const ETH_ADDRESS: [u8; ETH_ADDRESS_LEN] = [0xab; ETH_ADDRESS_LEN];
+    const MESSAGE: &[u8] = b"balance statement";
+
+    /// Secp256k1 precompile data for one signature, with the eth address, signature and
+    /// message laid out after the header and every offset pointing into `index`.
+    fn secp256k1_data(index: u8) -> Vec<u8> {
+        let eth_address_offset = SECP256K1_HEADER_LEN;
+        let signature_offset = eth_address_offset + ETH_ADDRESS_LEN;
+        let message_offset = signature_offset + 65;
+        let mut data = vec![1];
+        data.extend_from_slice(&(signature_offset as u16).to_le_bytes());
+        data.push(index);
+        data.extend_from_slice(&(eth_address_offset as u16).to_le_bytes());
+        data.push(index);
+        data.extend_from_slice(&(message_offset as u16).to_le_bytes());
+        data.extend_from_slice(&(MESSAGE.len() as u16).to_le_bytes());
+        data.push(index);
+        data.extend_from_slice(&ETH_ADDRESS);
+        data.extend_from_slice(&[0; 65]);
+        data.extend_from_slice(MESSAGE);
+        data
+    }
+
+    /// Verifies against a transaction of `precompile` followed by the attestation instruction.
+    fn verify(
+        program_id: Pubkey,
+        precompile: &[u8],
+        eth_address: &[u8; 20],
+        message: &[u8],
+    ) -> Result<()> {
+        let current = crate::ID;
+        #[allow(deprecated)]
+        let mut data = construct_instructions_data(&[
+            BorrowedInstruction {
+                program_id: &program_id,
+                accounts: Vec::new(),
+                data: precompile,
+            },
+            BorrowedInstruction {
+                program_id: &current,
+                accounts: Vec::new(),
+                data: &[],
+            },
+        ]);
+        // The current instruction index is stored in the last two bytes
+        let len = data.len();
+        data[len - 2..].copy_from_slice(&1u16.to_le_bytes());
+        let key = instructions_sysvar::ID;
+        let owner = Pubkey::default();
+        let mut lamports = 0;
+        let info = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+        verify_secp256k1_instruction(&info, eth_address, message)
+    }
+
+    #[test]
+    fn accepts_matching_precompile_instruction() {
+        verify(
+            secp256k1_program::ID,
+            &secp256k1_data(0),
+            &ETH_ADDRESS,
+            MESSAGE,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn rejects_instruction_of_another_program() {
+        assert_eq!(
+            verify(
+                Pubkey::new_unique(),
+                &secp256k1_data(0),
+                &ETH_ADDRESS,
+                MESSAGE
+            )
+            .unwrap_err(),
+            error!(ErrorCode::MissingSecp256k1Instruction)
+        );
+    }
+
+    #[test]
+    fn rejects_other_signature_counts() {
+        for num_signatures in [0, 2] {
+            let mut data = secp256k1_data(0);
+            data[0] = num_signatures;
+            assert_eq!(
+                verify(secp256k1_program::ID, &data, &ETH_ADDRESS, MESSAGE).unwrap_err(),
+                error!(ErrorCode::InvalidSecp256k1Instruction)
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_offsets_into_other_instructions() {
+        // Signature, eth address and message instruction indexes
+        for at in [3, 6, 11] {
+            let mut data = secp256k1_data(0);
+            data[at] = 1;
+            assert_eq!(
+                verify(secp256k1_program::ID, &data, &ETH_ADDRESS, MESSAGE).unwrap_err(),
+                error!(ErrorCode::InvalidSecp256k1Instruction)
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_other_signer_or_message() {
+        let data = secp256k1_data(0);
+        assert_eq!(
+            verify(secp256k1_program::ID, &data, &[0xcd; 20], MESSAGE).unwrap_err(),
+            error!(ErrorCode::AttestationMismatch)
+        );
+        assert_eq!(
+            verify(
+                secp256k1_program::ID,
+                &data,
+                &ETH_ADDRESS,
+                b"balance statemenT"
+            )
+            .unwrap_err(),
+            error!(ErrorCode::AttestationMismatch)
+        );
+    }
+}
@@ -1,44 +1,105 @@
 use anchor_lang::prelude::*;
 
+use crate::access_control::require_admin_or_operator;
 use crate::error::ErrorCode;
 use crate::event::AumUsdUpdated;
-use crate::state::{Operator, UnitasConfig};
-use crate::ADMIN_CONFIG_SEED;
+use crate::state::{fund_seed, PriceUpdateV2, ReservesUri, UnitasConfig, UsduConfig};
+use crate::{ADMIN_CONFIG_SEED, USDU_PRICE_FEED_SEED};
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct UpdateAumUsd<'info> {
     #[account(mut)]
     pub user: Signer<'info>,
     #[account(
         mut,
-        seeds = [ADMIN_CONFIG_SEED.as_bytes()],
+        seeds = [ADMIN_CONFIG_SEED.as_bytes(), fund_seed(config.fund_id).as_ref()],
         bump
     )]
     pub config: Account<'info, UnitasConfig>,
-    /// CHECK: This is the operator account, it is checked in the instruction
-    pub operator: Account<'info, Operator>,
+    /// CHECK: Validated by `require_admin_or_operator` unless the user is the admin
+    pub operator: UncheckedAccount<'info>,
+    /// CHECK: USDU program config, read for the token supply
+    #[account(address = config.usdu_config @ ErrorCode::InvalidUsduConfig)]
+    pub usdu_config: UncheckedAccount<'info>,
+    /// Republished with the NAV; required once `init_price_feed` has run
+    #[account(
+        mut,
+        seeds = [USDU_PRICE_FEED_SEED.as_bytes(), config.key().as_ref()],
+        bump
+    )]
+    pub price_feed: Option<Account<'info, PriceUpdateV2>>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn process_update_aum_usd(ctx: Context<UpdateAumUsd>, aum_usd: u128) -> Result<()> {
-    if !ctx.accounts.config.is_admin(&ctx.accounts.user.key()) {
-        require!(
-            ctx.accounts.operator.user == ctx.accounts.user.key(),
-            ErrorCode::InvalidOperator
-        );
-    } else {
-        require!(
-            ctx.accounts.config.is_admin(&ctx.accounts.user.key()),
-            ErrorCode::InvalidAdmin
-        );
-    }
-    let last_updated_timestamp = Clock::get()?.unix_timestamp;
-    ctx.accounts.config.aum_usd = aum_usd;
-    ctx.accounts.config.last_updated_timestamp = last_updated_timestamp;
-    emit!(AumUsdUpdated {
-        aum_usd,
-        last_updated_timestamp,
-        config: ctx.accounts.config.key(),
-    });
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateAumUsdArgs {
+    /// AUM held outside the registered asset tables, scaled to 6 decimals. The tables are
+    /// valued on chain into `onchain_aum_usd` and added to it, so they must not be counted here
+    pub aum_usd: u128,
+    /// `aum_sequence` the update was computed against; a racing update makes it fail
+    pub expected_sequence: u64,
+    /// When the reported AUM was observed; must be newer than the stored one
+    pub as_of: i64,
+    /// Merkle root of the per-account reserves report the AUM was built from
+    pub reserves_root: [u8; 32],
+    /// Where auditors can fetch that report
+    pub reserves_uri: String,
+}
+
+pub fn process_update_aum_usd(ctx: Context<UpdateAumUsd>, args: UpdateAumUsdArgs) -> Result<()> {
+    require_admin_or_operator(
+        &ctx.accounts.config,
+        &ctx.accounts.user.key(),
+        &ctx.accounts.operator,
+    )?;
+
+    let clock = Clock::get()?;
+    require_eq!(
+        args.expected_sequence,
+        ctx.accounts.config.aum_sequence,
+        ErrorCode::AumSequenceMismatch
+    );
+    require!(
+        args.as_of > ctx.accounts.config.aum_as_of,
+        ErrorCode::OutdatedAumUpdate
+    );
+    require!(
+        args.as_of <= clock.unix_timestamp,
+        ErrorCode::InvalidTimestamp
+    );
+
+    let reserves_uri = ReservesUri::new(&args.reserves_uri)?;
+    let usdu_supply = UsduConfig::read_total_supply(&ctx.accounts.usdu_config)?;
+    let config = &mut ctx.accounts.config;
+    config.aum_sequence = config
+        .aum_sequence
+        .checked_add(1)
+        .ok_or(ErrorCode::MathOverflow)?;
+    config.aum_as_of = args.as_of;
+    config.aum_usd = args.aum_usd;
+    config.last_updated_timestamp = clock.unix_timestamp;
+    config.last_updated_slot = clock.slot;
+    config.reserves_root = args.reserves_root;
+    config.reserves_uri = reserves_uri;
+    config.usdu_supply = usdu_supply;
+    let refresh = config.refresh_nav(clock.unix_timestamp)?;
+    PriceUpdateV2::republish_nav(ctx.accounts.price_feed.as_deref_mut(), config, &clock)?;
+    emit_event!(
+        ctx,
+        AumUsdUpdated {
+            aum_usd: config.aum_usd,
+            last_updated_timestamp: config.last_updated_timestamp,
+            last_updated_slot: config.last_updated_slot,
+            aum_sequence: config.aum_sequence,
+            aum_as_of: config.aum_as_of,
+            usdu_supply: config.usdu_supply,
+            nav: config.nav,
+            config: config.key(),
+            reserves_root: config.reserves_root,
+            reserves_uri: args.reserves_uri,
+        }
+    );
+    emit_nav_refresh!(ctx, refresh);
     Ok(())
 }
@@ -1,5 +1,9 @@
 use anchor_lang::prelude::*;
 
+#[macro_use]
+mod macros;
+
+pub mod access_control;
 pub mod constants;
 pub mod error;
 pub mod event;
@@ -17,8 +21,13 @@ declare_id!("UtyXF479UFC78u1ZV94JtXmnd7njb5FmSYZfFFdP1sT");
 pub mod unitas_oracle {
     use super::*;
 
-    pub fn init_admin_config(ctx: Context<InitAdminConfig>, usdu_config: Pubkey) -> Result<()> {
-        instructions::admin::process_init_admin_config(ctx, usdu_config)
+    /// Creates a fund's config. Funds other than 0 must be opened by fund 0's admin.
+    pub fn init_admin_config(
+        ctx: Context<InitAdminConfig>,
+        fund_id: u16,
+        usdu_config: Pubkey,
+    ) -> Result<()> {
+        instructions::admin::process_init_admin_config(ctx, fund_id, usdu_config)
     }
 
     pub fn propose_new_admin(ctx: Context<ProposeNewAdmin>) -> Result<()> {
@@ -37,6 +46,23 @@ pub mod unitas_oracle {
         instructions::admin::process_remove_operator(ctx, user)
     }
 
+    pub fn set_paused(ctx: Context<UpdateConfig>, paused: bool) -> Result<()> {
+        instructions::admin::process_set_paused(ctx, paused)
+    }
+
+    pub fn set_max_aum_age(ctx: Context<UpdateConfig>, max_aum_age: i64) -> Result<()> {
+        instructions::admin::process_set_max_aum_age(ctx, max_aum_age)
+    }
+
+    /// Reports published NAVs more than `nav_peg_band_bps` away from 1.0; zero disables it.
+    pub fn set_nav_peg_band(ctx: Context<UpdateConfig>, nav_peg_band_bps: u32) -> Result<()> {
+        instructions::admin::process_set_nav_peg_band(ctx, nav_peg_band_bps)
+    }
+
+    pub fn init_price_feed(ctx: Context<InitPriceFeed>, feed_id: [u8; 32]) -> Result<()> {
+        instructions::admin::process_init_price_feed(ctx, feed_id)
+    }
+
     pub fn create_asset_lookup_table(
         ctx: Context<CreateAssetLookupTable>,
         args: CreateAssetLookupTableArgs,
@@ -44,6 +70,60 @@ pub mod unitas_oracle {
         instructions::admin::process_create_asset_lookup_table(ctx, args)
     }
 
+    /// Closes an asset table without accounts and unregisters it from valuation rounds.
+    pub fn close_asset_lookup_table(ctx: Context<CloseAssetLookupTable>) -> Result<()> {
+        instructions::admin::process_close_asset_lookup_table(ctx)
+    }
+
+    /// Upgrades a config written with an older layout to the current one.
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        instructions::admin::process_migrate_config(ctx)
+    }
+
+    /// Upgrades an asset lookup table written with an older layout to the current one.
+    pub fn migrate_asset_lookup_table(ctx: Context<MigrateAssetLookupTable>) -> Result<()> {
+        instructions::admin::process_migrate_asset_lookup_table(ctx)
+    }
+
+    /// Replaces the oracles an asset is priced through, e.g. asset/SOL then SOL/USD, with the
+    /// Pyth feed id each of them must publish.
+    pub fn set_asset_price_path(
+        ctx: Context<SetAssetPricePath>,
+        legs: Vec<PriceLeg>,
+        feed_ids: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::admin::process_set_asset_price_path(ctx, legs, feed_ids)
+    }
+
+    /// Chooses whether a table is valued at its oracles' spot prices, EMA prices, or the lower
+    /// of the two.
+    pub fn set_asset_pricing_mode(
+        ctx: Context<SetAssetPricingMode>,
+        pricing_mode: PricingMode,
+    ) -> Result<()> {
+        instructions::admin::process_set_asset_pricing_mode(ctx, pricing_mode)
+    }
+
+    /// Values a stablecoin table at exactly $1 while its price stays within `peg_band_bps` of
+    /// it; zero values it at its price.
+    pub fn set_asset_peg_band(ctx: Context<SetAssetPegBand>, peg_band_bps: u16) -> Result<()> {
+        instructions::admin::process_set_asset_peg_band(ctx, peg_band_bps)
+    }
+
+    /// Prices a liquid staking token table through its stake pool's exchange rate, or stops
+    /// doing so when no stake pool is passed.
+    pub fn set_asset_stake_pool(ctx: Context<SetAssetStakePool>) -> Result<()> {
+        instructions::admin::process_set_asset_stake_pool(ctx)
+    }
+
+    pub fn add_custodian(ctx: Context<AddCustodian>, eth_address: [u8; 20]) -> Result<()> {
+        instructions::admin::process_add_custodian(ctx, eth_address)
+    }
+
+    pub fn remove_custodian(ctx: Context<RemoveCustodian>, eth_address: [u8; 20]) -> Result<()> {
+        instructions::admin::process_remove_custodian(ctx, eth_address)
+    }
+
     pub fn add_account(ctx: Context<AddAccount>, account: Pubkey) -> Result<()> {
         process_add_account(ctx, account)
     }
@@ -52,7 +132,82 @@ pub mod unitas_oracle {
         process_remove_account(ctx, account)
     }
 
-    pub fn update_aum_usd(ctx: Context<UpdateAumUsd>, aum_usd: u128) -> Result<()> {
-        process_update_aum_usd(ctx, aum_usd)
+    /// Adds several token account owners to an asset table at once, all or none.
+    pub fn add_accounts(ctx: Context<AddAccounts>, accounts: Vec<Pubkey>) -> Result<()> {
+        process_add_accounts(ctx, accounts)
+    }
+
+    /// Removes several token account owners from an asset table at once, all or none.
+    pub fn remove_accounts(ctx: Context<RemoveAccounts>, accounts: Vec<Pubkey>) -> Result<()> {
+        process_remove_accounts(ctx, accounts)
+    }
+
+    /// Labels a token account owner, for every asset table of the config.
+    pub fn set_account_label(
+        ctx: Context<SetAccountLabel>,
+        account: Pubkey,
+        label: String,
+        category: AccountCategory,
+    ) -> Result<()> {
+        process_set_account_label(ctx, account, label, category)
+    }
+
+    pub fn record_asset_valuation(
+        ctx: Context<RecordAssetValuation>,
+        args: RecordAssetValuationArgs,
+    ) -> Result<()> {
+        process_record_asset_valuation(ctx, args)
+    }
+
+    /// Opens a valuation round over every registered asset table.
+    pub fn begin_valuation(ctx: Context<BeginValuation>) -> Result<()> {
+        process_begin_valuation(ctx)
+    }
+
+    /// Values one asset table on chain and adds it to the open round. Permissionless.
+    pub fn accumulate_asset(ctx: Context<AccumulateAsset>) -> Result<()> {
+        process_accumulate_asset(ctx)
+    }
+
+    /// Publishes the round's total as the on-chain AUM. Permissionless.
+    pub fn finalize_valuation(ctx: Context<FinalizeValuation>) -> Result<()> {
+        process_finalize_valuation(ctx)
+    }
+
+    pub fn update_aum_usd(ctx: Context<UpdateAumUsd>, args: UpdateAumUsdArgs) -> Result<()> {
+        process_update_aum_usd(ctx, args)
+    }
+
+    /// Returns the published NAV with its freshness status through `set_return_data`.
+    /// With the `cpi` feature, `unitas_oracle::cpi::get_nav` returns it as a typed `NavData`.
+    pub fn get_nav(ctx: Context<GetNav>) -> Result<NavData> {
+        process_get_nav(ctx)
+    }
+
+    pub fn assert_nav_fresh(ctx: Context<AssertNav>, max_age: i64) -> Result<()> {
+        process_assert_nav_fresh(ctx, max_age)
+    }
+
+    pub fn assert_collateral_ratio(ctx: Context<AssertNav>, min_bps: u64) -> Result<()> {
+        process_assert_collateral_ratio(ctx, min_bps)
+    }
+
+    pub fn commit_liabilities(
+        ctx: Context<CommitLiabilities>,
+        args: CommitLiabilitiesArgs,
+    ) -> Result<()> {
+        process_commit_liabilities(ctx, args)
+    }
+
+    pub fn submit_custodian_attestation(
+        ctx: Context<SubmitCustodianAttestation>,
+        args: CustodianAttestationArgs,
+    ) -> Result<()> {
+        process_submit_custodian_attestation(ctx, args)
+    }
+
+    /// Drops a custodian balance attested longer than `max_aum_age` ago from the AUM.
+    pub fn expire_custodian_attestation(ctx: Context<ExpireCustodianAttestation>) -> Result<()> {
+        process_expire_custodian_attestation(ctx)
     }
 }
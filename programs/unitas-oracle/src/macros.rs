@@ -0,0 +1,101 @@
+/// Emits an event through Anchor's self-CPI event authority, so it is recorded in the
+/// transaction's inner instructions instead of (truncatable) program logs. The accounts
+/// struct must carry `#[event_cpi]`.
+///
+/// While the `legacy-log-events` feature is enabled the event is also written to the
+/// program logs, for indexers that still parse `emit!` output.
+macro_rules! emit_event {
+    ($ctx:ident, $event:expr) => {{
+        let event = $event;
+        #[cfg(feature = "legacy-log-events")]
+        anchor_lang::prelude::emit!(event);
+        let ctx = &$ctx;
+        anchor_lang::prelude::emit_cpi!(event);
+    }};
+}
+
+/// Emits `StablecoinDepegged` or `StablecoinRepegged` for a peg change returned by
+/// `AssetLookupTable::update_peg`, naming `ctx.accounts.asset_lookup_table`. `price` is the
+/// price before the peg band was applied.
+macro_rules! emit_peg_change {
+    (
+        $ctx:ident,
+        $peg_change:expr,
+        $asset_mint:expr,
+        $price:expr,
+        $price_exponent:expr,
+        $peg_band_bps:expr
+    ) => {{
+        match $peg_change {
+            Some(true) => emit_event!(
+                $ctx,
+                $crate::event::StablecoinDepegged {
+                    lookup_table: $ctx.accounts.asset_lookup_table.key(),
+                    asset_mint: $asset_mint,
+                    price: $price,
+                    price_exponent: $price_exponent,
+                    peg_band_bps: $peg_band_bps,
+                }
+            ),
+            Some(false) => emit_event!(
+                $ctx,
+                $crate::event::StablecoinRepegged {
+                    lookup_table: $ctx.accounts.asset_lookup_table.key(),
+                    asset_mint: $asset_mint,
+                    price: $price,
+                    price_exponent: $price_exponent,
+                    peg_band_bps: $peg_band_bps,
+                }
+            ),
+            None => {}
+        }
+    }};
+}
+
+/// Emits the events of a `NavRefresh` returned by `UnitasConfig::refresh_nav`: the NAV leaving
+/// or returning to its peg band and each collateral ratio threshold crossed, reading the
+/// refreshed figures from `ctx.accounts.config`. Like `emit_event!`, the accounts struct must
+/// carry `#[event_cpi]`.
+macro_rules! emit_nav_refresh {
+    ($ctx:ident, $refresh:expr) => {{
+        let refresh: $crate::state::NavRefresh = $refresh;
+        match refresh.peg {
+            $crate::state::NavPegCheck::Within => {}
+            $crate::state::NavPegCheck::OffPeg {
+                direction,
+                deviation_bps,
+            } => emit_event!(
+                $ctx,
+                $crate::event::NavPegDeviation {
+                    config: $ctx.accounts.config.key(),
+                    nav: $ctx.accounts.config.nav,
+                    direction,
+                    deviation_bps,
+                    peg_band_bps: $ctx.accounts.config.nav_peg_band_bps,
+                    off_peg_since: $ctx.accounts.config.nav_off_peg_since,
+                }
+            ),
+            $crate::state::NavPegCheck::Restored { off_peg_since } => emit_event!(
+                $ctx,
+                $crate::event::NavPegRestored {
+                    config: $ctx.accounts.config.key(),
+                    nav: $ctx.accounts.config.nav,
+                    peg_band_bps: $ctx.accounts.config.nav_peg_band_bps,
+                    off_peg_since,
+                }
+            ),
+        }
+        for crossing in refresh.collateral_crossings {
+            emit_event!(
+                $ctx,
+                $crate::event::CollateralRatioThresholdCrossed {
+                    config: $ctx.accounts.config.key(),
+                    threshold_bps: crossing.threshold_bps,
+                    is_below: crossing.is_below,
+                    collateral_ratio_bps: $ctx.accounts.config.collateral_ratio_bps,
+                    collateral_surplus_usd: $ctx.accounts.config.collateral_surplus_usd,
+                }
+            );
+        }
+    }};
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+/// Maximum length, in bytes, of an account label.
+pub const MAX_ACCOUNT_LABEL_LEN: usize = 32;
+
+/// What a tracked token account owner is, for auditing the holdings list.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum AccountCategory {
+    #[default]
+    Unspecified,
+    Custodian,
+    Exchange,
+    Treasury,
+    Protocol,
+    Other,
+}
+
+/// Human-readable metadata of a token account owner, shared by every asset table of a config.
+#[account]
+#[derive(Default)]
+pub struct AccountLabel {
+    pub config: Pubkey,
+    pub account: Pubkey,
+    // UTF-8, zero padded
+    pub label: [u8; MAX_ACCOUNT_LABEL_LEN],
+    pub category: AccountCategory,
+}
+
+impl AccountLabel {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // config
+        32 + // account
+        MAX_ACCOUNT_LABEL_LEN + // label
+        1; // category
+
+    pub fn set_label(&mut self, label: &str) -> Result<()> {
+        require!(
+            label.len() <= MAX_ACCOUNT_LABEL_LEN,
+            ErrorCode::AccountLabelTooLong
+        );
+        self.label = [0; MAX_ACCOUNT_LABEL_LEN];
+        self.label[..label.len()].copy_from_slice(label.as_bytes());
+        Ok(())
+    }
+
+    /// The label without its padding; empty when none is set.
+    pub fn label(&self) -> &str {
+        let len = self
+            .label
+            .iter()
+            .position(|&b| b == 0)
+            .unwrap_or(MAX_ACCOUNT_LABEL_LEN);
+        std::str::from_utf8(&self.label[..len]).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn label_round_trips_without_padding() {
+        let mut account_label = AccountLabel::default();
+        account_label.set_label("Custodian A cold wallet").unwrap();
+        assert_eq!(account_label.label(), "Custodian A cold wallet");
+
+        account_label.set_label("Hot").unwrap();
+        assert_eq!(account_label.label(), "Hot");
+
+        let longest = "x".repeat(MAX_ACCOUNT_LABEL_LEN);
+        account_label.set_label(&longest).unwrap();
+        assert_eq!(account_label.label(), longest);
+    }
+
+    #[test]
+    fn rejects_label_longer_than_the_limit() {
+        let mut account_label = AccountLabel::default();
+        account_label.set_label("Treasury").unwrap();
+        assert_eq!(
+            account_label
+                .set_label(&"x".repeat(MAX_ACCOUNT_LABEL_LEN + 1))
+                .unwrap_err(),
+            error!(ErrorCode::AccountLabelTooLong)
+        );
+        assert_eq!(account_label.label(), "Treasury");
+    }
+}
@@ -1,5 +1,9 @@
 use crate::error::ErrorCode;
+use crate::state::{price_feed_ids_hash, PriceLeg, PricingMode, MAX_PRICE_LEGS};
+use crate::{BPS_DENOMINATOR, NATIVE_SOL_ASSET_MINT, STAKE_ASSET_MINT, STAKE_PROGRAM_ID};
 use anchor_lang::prelude::*;
+use anchor_spl::associated_token::get_associated_token_address;
+use anchor_spl::token::{self, TokenAccount};
 
 pub const MAX_ACCOUNTS_PER_ASSET: usize = 16;
 
@@ -8,6 +12,7 @@ pub const MAX_ACCOUNTS_PER_ASSET: usize = 16;
 #[derive(Debug)]
 pub struct AssetLookupTable {
     // 8-byte alignment
+    pub config: Pubkey,
     pub asset_mint: Pubkey,
     pub oracle_account: Pubkey,
     pub token_account_owners: [Pubkey; MAX_ACCOUNTS_PER_ASSET],
@@ -15,13 +20,246 @@ pub struct AssetLookupTable {
     pub token_account_owners_len: u32,
     // 1-byte alignment
     pub decimals: u8,
-    // trailing paddings
-    pub paddings: [u8; 3],
+    // Layout version, see `ASSET_LOOKUP_TABLE_VERSION`; zero in tables created before versioning
+    pub version: u8,
+    pub paddings: [u8; 2],
+
+    // Last valuation of the asset, on or off chain; zero until the first one
+    pub last_price: i64,
+    pub last_total_amount: u64,
+    // USD value scaled to 6 decimals, like the AUM
+    pub last_usd_value: u64,
+    pub last_valued_at: i64,
+    pub last_price_exponent: i32,
+    // `ValuationSource` of the last valuation
+    pub last_valuation_source: u8,
+    pub valuation_paddings: [u8; 3],
+    // Valuation round this table was last accumulated into
+    pub last_accumulated_round: u64,
+
+    // `AssetKind`, derived from the asset mint when the table is created
+    pub asset_kind: u8,
+    pub kind_paddings: [u8; 7],
+
+    // SPL stake pool whose exchange rate converts the oracle's SOL price into the price of
+    // the pool token; default when the oracle prices the asset directly
+    pub stake_pool: Pubkey,
+
+    // Oracles chained after `oracle_account` to price the asset, see `price_path`
+    pub price_legs: [Pubkey; MAX_PRICE_LEGS - 1],
+    pub price_legs_len: u8,
+    // Bit `i` set when leg `i` of the price path divides, `oracle_account` being leg 0
+    pub price_leg_divide_mask: u8,
+    pub price_path_paddings: [u8; 6],
+
+    // Stablecoins with a band are valued at exactly $1 while their price deviates from it by at
+    // most `peg_band_bps`; zero values the asset at its price
+    pub peg_band_bps: u16,
+    // Set while the price is outside the band
+    pub is_depegged: u8,
+    // `PricingMode` of every valuation of the asset
+    pub pricing_mode: u8,
+    pub peg_paddings: [u8; 4],
+
+    // `price_feed_ids_hash` of the Pyth feed ids the price path's oracles must publish
+    pub price_feed_ids_hash: [u8; 32],
+
+    // Reserved for future fields, so they can be added without a realloc
+    pub reserved: [u64; 7],
+}
+
+/// How the holdings of an asset table are found and measured.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum AssetKind {
+    /// Associated token accounts of the owners for the asset mint
+    #[default]
+    SplToken,
+    /// Lamports of the owners themselves, less their rent-exempt minimum
+    NativeSol,
+    /// Stake accounts, tracked as owners, less their rent-exempt reserve
+    StakeAccount,
+}
+
+impl AssetKind {
+    pub fn from_asset_mint(asset_mint: &Pubkey) -> Self {
+        match *asset_mint {
+            NATIVE_SOL_ASSET_MINT => AssetKind::NativeSol,
+            STAKE_ASSET_MINT => AssetKind::StakeAccount,
+            _ => AssetKind::SplToken,
+        }
+    }
+
+    /// Amount held by a holding account, in the asset's base units. Accounts that don't
+    /// exist, or aren't owned by the program the asset kind expects, hold nothing.
+    pub fn holding_amount(
+        &self,
+        owner_program: &Pubkey,
+        lamports: u64,
+        data: &[u8],
+        rent: &Rent,
+    ) -> Result<u64> {
+        match self {
+            AssetKind::SplToken => {
+                if *owner_program != token::ID {
+                    return Ok(0);
+                }
+                Ok(TokenAccount::try_deserialize(&mut &data[..])?.amount)
+            }
+            AssetKind::NativeSol => Ok(lamports.saturating_sub(rent.minimum_balance(data.len()))),
+            AssetKind::StakeAccount => {
+                if *owner_program != STAKE_PROGRAM_ID {
+                    return Ok(0);
+                }
+                // `StakeStateV2`: a u32 tag, then `Meta` starting with the rent-exempt reserve
+                let tag = data.get(..4).ok_or(ErrorCode::InvalidStakeAccount)?;
+                match u32::from_le_bytes(tag.try_into().unwrap()) {
+                    // Initialized or delegated
+                    1 | 2 => {
+                        let reserve = data.get(4..12).ok_or(ErrorCode::InvalidStakeAccount)?;
+                        let reserve = u64::from_le_bytes(reserve.try_into().unwrap());
+                        Ok(lamports.saturating_sub(reserve))
+                    }
+                    _ => Ok(0),
+                }
+            }
+        }
+    }
+}
+
+/// Who produced an asset table's last valuation.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum ValuationSource {
+    /// Recorded by the operator with `record_asset_valuation`; also what tables valued before
+    /// the source was tracked hold
+    #[default]
+    Operator,
+    /// Computed on chain by `accumulate_asset`
+    OnChain,
+}
+
+impl TryFrom<u8> for ValuationSource {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(ValuationSource::Operator),
+            1 => Ok(ValuationSource::OnChain),
+            _ => err!(ErrorCode::UnknownValuationSource),
+        }
+    }
+}
+
+impl TryFrom<u8> for AssetKind {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(AssetKind::SplToken),
+            1 => Ok(AssetKind::NativeSol),
+            2 => Ok(AssetKind::StakeAccount),
+            _ => err!(ErrorCode::UnknownAssetKind),
+        }
+    }
 }
 
 impl AssetLookupTable {
     pub const LEN: usize = 8 + std::mem::size_of::<AssetLookupTable>();
 
+    pub fn asset_kind(&self) -> Result<AssetKind> {
+        AssetKind::try_from(self.asset_kind)
+    }
+
+    pub fn last_valuation_source(&self) -> Result<ValuationSource> {
+        ValuationSource::try_from(self.last_valuation_source)
+    }
+
+    /// Account whose balance `owner` holds the asset in: its associated token account for
+    /// SPL tokens, or the owner itself for native SOL and stake accounts.
+    pub fn holding_address(&self, owner: &Pubkey) -> Result<Pubkey> {
+        Ok(match self.asset_kind()? {
+            AssetKind::SplToken => get_associated_token_address(owner, &self.asset_mint),
+            AssetKind::NativeSol | AssetKind::StakeAccount => *owner,
+        })
+    }
+
+    pub fn pricing_mode(&self) -> Result<PricingMode> {
+        PricingMode::try_from(self.pricing_mode)
+    }
+
+    /// Oracles whose prices are chained to price the asset, `oracle_account` first.
+    pub fn price_path(&self) -> Vec<PriceLeg> {
+        std::iter::once(&self.oracle_account)
+            .chain(&self.price_legs[..self.price_legs_len as usize])
+            .enumerate()
+            .map(|(i, oracle)| PriceLeg {
+                oracle: *oracle,
+                divide: self.price_leg_divide_mask & (1 << i) != 0,
+            })
+            .collect()
+    }
+
+    /// Replaces the price path; `feed_ids[i]` is the Pyth feed id leg `i`'s oracle must publish.
+    pub fn set_price_path(&mut self, legs: &[PriceLeg], feed_ids: &[[u8; 32]]) -> Result<()> {
+        require!(
+            !legs.is_empty() && legs.len() <= MAX_PRICE_LEGS && feed_ids.len() == legs.len(),
+            ErrorCode::InvalidPricePath
+        );
+        self.oracle_account = legs[0].oracle;
+        self.price_legs = [Pubkey::default(); MAX_PRICE_LEGS - 1];
+        self.price_leg_divide_mask = 0;
+        for (i, leg) in legs.iter().enumerate() {
+            if i > 0 {
+                self.price_legs[i - 1] = leg.oracle;
+            }
+            if leg.divide {
+                self.price_leg_divide_mask |= 1 << i;
+            }
+        }
+        self.price_legs_len = (legs.len() - 1) as u8;
+        self.price_feed_ids_hash = price_feed_ids_hash(feed_ids);
+        Ok(())
+    }
+
+    /// Checks the feed ids published by the price path's oracles, leg by leg, against the ones
+    /// the path was set with. Tables whose path predates the check match no feed ids.
+    pub fn check_price_feed_ids(&self, feed_ids: &[[u8; 32]]) -> Result<()> {
+        require!(
+            self.price_feed_ids_hash == price_feed_ids_hash(feed_ids),
+            ErrorCode::PriceFeedIdMismatch
+        );
+        Ok(())
+    }
+
+    /// Price to value the asset at, with the exponent of `price`, and whether a pegged
+    /// stablecoin is outside its band and therefore valued at `price`.
+    pub fn peg_price(&self, price: i64, exponent: i32) -> Result<(i64, bool)> {
+        if self.peg_band_bps == 0 {
+            return Ok((price, false));
+        }
+        require!(exponent <= 0, ErrorCode::InvalidOraclePrice);
+        let one = 10i128
+            .checked_pow(exponent.unsigned_abs())
+            .filter(|one| *one <= i64::MAX as i128)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let deviation_bps = (price as i128 - one).abs() * BPS_DENOMINATOR as i128 / one;
+        if deviation_bps <= self.peg_band_bps as i128 {
+            Ok((one as i64, false))
+        } else {
+            Ok((price, true))
+        }
+    }
+
+    /// Applies `peg_price` and records whether the asset is depegged. Also returns the new peg
+    /// state when the price left or returned to the band, for the caller to report.
+    pub fn update_peg(&mut self, price: i64, exponent: i32) -> Result<(i64, Option<bool>)> {
+        let (price, is_depegged) = self.peg_price(price, exponent)?;
+        let changed = is_depegged != (self.is_depegged != 0);
+        self.is_depegged = is_depegged as u8;
+        Ok((price, changed.then_some(is_depegged)))
+    }
+
     pub fn add_token_account_owner(&mut self, account: Pubkey) -> Result<()> {
         let len = self.token_account_owners_len as usize;
         require!(len < MAX_ACCOUNTS_PER_ASSET, ErrorCode::AccountLimitReached);
@@ -37,6 +275,29 @@ impl AssetLookupTable {
         Ok(())
     }
 
+    /// Caches a valuation of the asset. Valuations must not go back in time.
+    pub fn record_valuation(
+        &mut self,
+        price: i64,
+        price_exponent: i32,
+        total_amount: u64,
+        usd_value: u64,
+        valued_at: i64,
+        source: ValuationSource,
+    ) -> Result<()> {
+        require!(
+            valued_at >= self.last_valued_at,
+            ErrorCode::OutdatedValuation
+        );
+        self.last_valuation_source = source as u8;
+        self.last_price = price;
+        self.last_price_exponent = price_exponent;
+        self.last_total_amount = total_amount;
+        self.last_usd_value = usd_value;
+        self.last_valued_at = valued_at;
+        Ok(())
+    }
+
     pub fn remove_token_account_owner(&mut self, account: Pubkey) -> Result<()> {
         let len = self.token_account_owners_len as usize;
         if len == 0 {
@@ -61,4 +322,321 @@ impl AssetLookupTable {
             err!(ErrorCode::InvalidAccount)
         }
     }
+
+    /// Adds every account in `accounts`, or none of them if any one can't be added.
+    pub fn add_token_account_owners(&mut self, accounts: &[Pubkey]) -> Result<()> {
+        require_distinct(accounts)?;
+        let mut updated = *self;
+        for account in accounts {
+            updated.add_token_account_owner(*account)?;
+        }
+        *self = updated;
+        Ok(())
+    }
+
+    /// Removes every account in `accounts`, or none of them if any one isn't tracked.
+    pub fn remove_token_account_owners(&mut self, accounts: &[Pubkey]) -> Result<()> {
+        require_distinct(accounts)?;
+        let mut updated = *self;
+        for account in accounts {
+            updated.remove_token_account_owner(*account)?;
+        }
+        *self = updated;
+        Ok(())
+    }
+}
+
+fn require_distinct(accounts: &[Pubkey]) -> Result<()> {
+    require!(!accounts.is_empty(), ErrorCode::EmptyAccountList);
+    for (i, account) in accounts.iter().enumerate() {
+        require!(
+            !accounts[..i].contains(account),
+            ErrorCode::DuplicateAccount
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(owners: &[Pubkey]) -> AssetLookupTable {
+        let mut table: AssetLookupTable = bytemuck::Zeroable::zeroed();
+        for owner in owners {
+            table.add_token_account_owner(*owner).unwrap();
+        }
+        table
+    }
+
+    fn owners(table: &AssetLookupTable) -> &[Pubkey] {
+        &table.token_account_owners[..table.token_account_owners_len as usize]
+    }
+
+    #[test]
+    fn asset_kind_follows_the_asset_mint() {
+        let mint = Pubkey::new_unique();
+        assert_eq!(AssetKind::from_asset_mint(&mint), AssetKind::SplToken);
+        assert_eq!(
+            AssetKind::from_asset_mint(&NATIVE_SOL_ASSET_MINT),
+            AssetKind::NativeSol
+        );
+        assert_eq!(
+            AssetKind::from_asset_mint(&STAKE_ASSET_MINT),
+            AssetKind::StakeAccount
+        );
+
+        let owner = Pubkey::new_unique();
+        let mut table = table(&[]);
+        table.asset_mint = mint;
+        assert_eq!(
+            table.holding_address(&owner).unwrap(),
+            get_associated_token_address(&owner, &mint)
+        );
+        table.asset_kind = AssetKind::NativeSol as u8;
+        assert_eq!(table.holding_address(&owner).unwrap(), owner);
+        table.asset_kind = 3;
+        assert_eq!(
+            table.holding_address(&owner).unwrap_err(),
+            error!(ErrorCode::UnknownAssetKind)
+        );
+    }
+
+    #[test]
+    fn native_sol_excludes_the_rent_exempt_minimum() {
+        let rent = Rent::default();
+        let minimum = rent.minimum_balance(0);
+        let amount = AssetKind::NativeSol
+            .holding_amount(&System::id(), minimum + 5, &[], &rent)
+            .unwrap();
+        assert_eq!(amount, 5);
+        // Wallets below the minimum hold nothing rather than underflowing
+        let amount = AssetKind::NativeSol
+            .holding_amount(&System::id(), minimum - 1, &[], &rent)
+            .unwrap();
+        assert_eq!(amount, 0);
+    }
+
+    #[test]
+    fn stake_accounts_exclude_the_rent_exempt_reserve() {
+        let rent = Rent::default();
+        let mut data = vec![0u8; 200];
+        data[..4].copy_from_slice(&2u32.to_le_bytes());
+        data[4..12].copy_from_slice(&2_282_880u64.to_le_bytes());
+        let amount = AssetKind::StakeAccount
+            .holding_amount(&STAKE_PROGRAM_ID, 1_002_282_880, &data, &rent)
+            .unwrap();
+        assert_eq!(amount, 1_000_000_000);
+
+        // Uninitialized stake accounts and accounts of other programs hold nothing
+        data[..4].copy_from_slice(&0u32.to_le_bytes());
+        for owner in [STAKE_PROGRAM_ID, System::id()] {
+            let amount = AssetKind::StakeAccount
+                .holding_amount(&owner, 1_002_282_880, &data, &rent)
+                .unwrap();
+            assert_eq!(amount, 0);
+        }
+    }
+
+    #[test]
+    fn price_path_starts_with_the_oracle_account() {
+        let mut table = table(&[]);
+        table.oracle_account = Pubkey::new_unique();
+        assert_eq!(
+            table.price_path(),
+            [PriceLeg {
+                oracle: table.oracle_account,
+                divide: false
+            }]
+        );
+
+        let legs = [(); MAX_PRICE_LEGS].map(|_| PriceLeg {
+            oracle: Pubkey::new_unique(),
+            divide: false,
+        });
+        let mut path = legs.to_vec();
+        path[2].divide = true;
+        let feed_ids = [[1; 32], [2; 32], [3; 32]];
+        table.set_price_path(&path, &feed_ids).unwrap();
+        assert_eq!(table.oracle_account, path[0].oracle);
+        assert_eq!(table.price_path(), path);
+
+        // A shorter path clears the legs it no longer uses
+        table.set_price_path(&path[..1], &feed_ids[..1]).unwrap();
+        assert_eq!(table.price_path(), path[..1]);
+        assert_eq!(table.price_leg_divide_mask, 0);
+
+        assert_eq!(
+            table.set_price_path(&[], &[]).unwrap_err(),
+            error!(ErrorCode::InvalidPricePath)
+        );
+        let too_long = [path.as_slice(), &path[..1]].concat();
+        assert_eq!(
+            table.set_price_path(&too_long, &[[1; 32]; 4]).unwrap_err(),
+            error!(ErrorCode::InvalidPricePath)
+        );
+        // Every leg needs its feed id
+        assert_eq!(
+            table.set_price_path(&path, &feed_ids[..2]).unwrap_err(),
+            error!(ErrorCode::InvalidPricePath)
+        );
+    }
+
+    #[test]
+    fn oracles_must_publish_the_feeds_of_their_legs() {
+        let mut table = table(&[]);
+        // A path set before feed ids were stored matches none
+        assert_eq!(
+            table.check_price_feed_ids(&[[0; 32]]).unwrap_err(),
+            error!(ErrorCode::PriceFeedIdMismatch)
+        );
+
+        let legs = [(); 2].map(|_| PriceLeg {
+            oracle: Pubkey::new_unique(),
+            divide: false,
+        });
+        let feed_ids = [[1; 32], [2; 32]];
+        table.set_price_path(&legs, &feed_ids).unwrap();
+        table.check_price_feed_ids(&feed_ids).unwrap();
+
+        // Swapped, missing or foreign feeds are rejected
+        for published in [
+            vec![[2; 32], [1; 32]],
+            vec![[1; 32]],
+            vec![[1; 32], [3; 32]],
+            vec![[1; 32], [2; 32], [2; 32]],
+        ] {
+            assert_eq!(
+                table.check_price_feed_ids(&published).unwrap_err(),
+                error!(ErrorCode::PriceFeedIdMismatch)
+            );
+        }
+    }
+
+    #[test]
+    fn pegged_stablecoin_is_worth_one_dollar_within_its_band() {
+        let mut table = table(&[]);
+        // Without a band the price is used as is
+        assert_eq!(
+            table.peg_price(99_900_000, -8).unwrap(),
+            (99_900_000, false)
+        );
+
+        table.peg_band_bps = 50;
+        assert_eq!(
+            table.peg_price(99_900_000, -8).unwrap(),
+            (100_000_000, false)
+        );
+        assert_eq!(
+            table.peg_price(100_500_000, -8).unwrap(),
+            (100_000_000, false)
+        );
+        assert_eq!(table.peg_price(99_400_000, -8).unwrap(), (99_400_000, true));
+        assert_eq!(
+            table.peg_price(100_600_000, -8).unwrap(),
+            (100_600_000, true)
+        );
+    }
+
+    #[test]
+    fn reports_peg_changes_once() {
+        let mut table = table(&[]);
+        table.peg_band_bps = 50;
+        assert_eq!(
+            table.update_peg(99_900_000, -8).unwrap(),
+            (100_000_000, None)
+        );
+        assert_eq!(
+            table.update_peg(99_400_000, -8).unwrap(),
+            (99_400_000, Some(true))
+        );
+        assert_eq!(
+            table.update_peg(99_300_000, -8).unwrap(),
+            (99_300_000, None)
+        );
+        assert_eq!(
+            table.update_peg(100_000_000, -8).unwrap(),
+            (100_000_000, Some(false))
+        );
+    }
+
+    #[test]
+    fn records_who_valued_the_asset() {
+        let mut table = table(&[]);
+        table
+            .record_valuation(100, -2, 5, 5_000_000, 1_000, ValuationSource::OnChain)
+            .unwrap();
+        assert_eq!(
+            table.last_valuation_source().unwrap(),
+            ValuationSource::OnChain
+        );
+        table
+            .record_valuation(101, -2, 5, 5_050_000, 1_010, ValuationSource::Operator)
+            .unwrap();
+        assert_eq!(
+            table.last_valuation_source().unwrap(),
+            ValuationSource::Operator
+        );
+        assert_eq!(
+            table
+                .record_valuation(100, -2, 5, 5_000_000, 1_005, ValuationSource::Operator)
+                .unwrap_err(),
+            error!(ErrorCode::OutdatedValuation)
+        );
+    }
+
+    #[test]
+    fn adds_and_removes_accounts_in_batches() {
+        let [a, b, c] = [(); 3].map(|_| Pubkey::new_unique());
+        let mut table = table(&[a]);
+
+        table.add_token_account_owners(&[b, c]).unwrap();
+        assert_eq!(owners(&table), [a, b, c]);
+
+        table.remove_token_account_owners(&[a, c]).unwrap();
+        assert_eq!(owners(&table), [b]);
+    }
+
+    #[test]
+    fn batch_changes_are_all_or_nothing() {
+        let [a, b, c] = [(); 3].map(|_| Pubkey::new_unique());
+        let mut table = table(&[a]);
+
+        assert_eq!(
+            table.add_token_account_owners(&[b, a]).unwrap_err(),
+            error!(ErrorCode::AccountAlreadyAdded)
+        );
+        assert_eq!(
+            table.remove_token_account_owners(&[a, c]).unwrap_err(),
+            error!(ErrorCode::InvalidAccount)
+        );
+        let full: Vec<Pubkey> = (0..MAX_ACCOUNTS_PER_ASSET)
+            .map(|_| Pubkey::new_unique())
+            .collect();
+        assert_eq!(
+            table.add_token_account_owners(&full).unwrap_err(),
+            error!(ErrorCode::AccountLimitReached)
+        );
+        assert_eq!(owners(&table), [a]);
+    }
+
+    #[test]
+    fn rejects_duplicate_or_empty_batches() {
+        let [a, b] = [(); 2].map(|_| Pubkey::new_unique());
+        let mut table = table(&[a]);
+
+        assert_eq!(
+            table.add_token_account_owners(&[b, b]).unwrap_err(),
+            error!(ErrorCode::DuplicateAccount)
+        );
+        assert_eq!(
+            table.remove_token_account_owners(&[a, a]).unwrap_err(),
+            error!(ErrorCode::DuplicateAccount)
+        );
+        assert_eq!(
+            table.add_token_account_owners(&[]).unwrap_err(),
+            error!(ErrorCode::EmptyAccountList)
+        );
+        assert_eq!(owners(&table), [a]);
+    }
 }
@@ -1,31 +1,340 @@
 use anchor_lang::prelude::*;
 
+use crate::error::ErrorCode;
+use crate::{BPS_DENOMINATOR, COLLATERAL_RATIO_THRESHOLDS_BPS, MAX_RESERVES_URI_LEN, NAV_DECIMALS};
+
 #[account]
-#[derive(Default)]
+#[derive(Debug, Default)]
 pub struct UnitasConfig {
+    // Layout version, see `CONFIG_VERSION`
+    pub version: u8,
+
+    // Fund this config serves; part of its PDA seeds, see `fund_seed`
+    pub fund_id: u16,
+
     // Auth
     pub admin: Pubkey,
     pub pending_admin: Pubkey,
 
-    // AUM
+    // AUM reported by the operator, excluding the registered asset tables valued on chain
     pub aum_usd: u128,
     pub last_updated_timestamp: i64,
+    pub last_updated_slot: u64,
+    // Incremented on every AUM update; updates must name the sequence they build on
+    pub aum_sequence: u64,
+    // Observation time of the reported AUM, strictly increasing across updates
+    pub aum_as_of: i64,
+    // Sum of the latest verified custodian balance statements
+    pub attested_aum_usd: u128,
+
+    // NAV published from the total AUM and the last read USDU supply
+    pub usdu_supply: u128,
+    pub nav: u64,
+
+    // Proof of reserves: Merkle root and location of the report behind `aum_usd`
+    pub reserves_root: [u8; 32],
+    pub reserves_uri: ReservesUri,
+
+    // Proof of liabilities: Merkle-sum root over USDU holder balances and its total
+    pub liabilities_root: [u8; 32],
+    pub total_liabilities: u128,
+    pub liabilities_updated_timestamp: i64,
 
     // Other configs
     pub usdu_config: Pubkey,
+    pub is_paused: bool,
+    pub max_aum_age: i64,
+
+    // On-chain AUM: total of the last finalized valuation round over the registered asset
+    // tables. Assets valued here must be left out of the operator-reported `aum_usd`.
+    pub onchain_aum_usd: u128,
+    pub onchain_aum_timestamp: i64,
+    // Asset lookup tables registered under this config; every round must value all of them
+    pub asset_count: u32,
+
+    // Collateralization as of the last NAV refresh: total AUM over USDU supply (zero while
+    // USDU has no supply) and total AUM minus supply, negative in deficit
+    pub collateral_ratio_bps: u64,
+    pub collateral_surplus_usd: i128,
+    // Bit `i` is set while the ratio is below `COLLATERAL_RATIO_THRESHOLDS_BPS[i]`
+    pub collateral_breaches: u32,
+
+    // NAV peg monitoring: band around a NAV of 1.0 (zero disables it), the signed deviation of
+    // the last published NAV, and when the NAV left the band (zero while within it)
+    pub nav_peg_band_bps: u32,
+    pub nav_deviation_bps: i64,
+    pub nav_off_peg_since: i64,
+
+    // Set by `init_price_feed`; from then on every NAV change must republish the feed
+    pub has_price_feed: bool,
+    // Bump of the config PDA, so readers can check its address without searching for the bump
+    pub bump: u8,
+    pub paddings: [u8; 2],
+
+    // Reserved for future fields, so they can be added without a realloc
+    pub reserved: [u32; 12],
 }
 
 impl UnitasConfig {
     pub const LEN: usize = 8 + // discriminator
+        1 +  // version
+        2 +  // fund_id
         32 + // admin
         32 + // pending_admin
         16 + // aum_usd
         8 +  // last_updated_timestamp
-        32; // usdu_config
+        8 +  // last_updated_slot
+        8 +  // aum_sequence
+        8 +  // aum_as_of
+        16 + // attested_aum_usd
+        16 + // usdu_supply
+        8 +  // nav
+        32 + // reserves_root
+        1 + MAX_RESERVES_URI_LEN + // reserves_uri
+        32 + // liabilities_root
+        16 + // total_liabilities
+        8 +  // liabilities_updated_timestamp
+        32 + // usdu_config
+        1 +  // is_paused
+        8 +  // max_aum_age
+        16 + // onchain_aum_usd
+        8 +  // onchain_aum_timestamp
+        4 +  // asset_count
+        8 +  // collateral_ratio_bps
+        16 + // collateral_surplus_usd
+        4 +  // collateral_breaches
+        4 +  // nav_peg_band_bps
+        8 +  // nav_deviation_bps
+        8 +  // nav_off_peg_since
+        1 +  // has_price_feed
+        1 +  // bump
+        2 +  // paddings
+        48; // reserved
 
     pub fn is_admin(&self, key: &Pubkey) -> bool {
         self.admin == *key
     }
+
+    /// Total AUM: the operator-reported value, attested custodian balances and the on-chain
+    /// valuation of the registered assets.
+    pub fn total_aum_usd(&self) -> Result<u128> {
+        self.aum_usd
+            .checked_add(self.attested_aum_usd)
+            .and_then(|aum| aum.checked_add(self.onchain_aum_usd))
+            .ok_or_else(|| error!(ErrorCode::MathOverflow))
+    }
+
+    /// Recomputes `nav`, its peg deviation and the collateral figures from the total AUM and
+    /// the stored USDU supply, returning what the caller should report with `emit_nav_refresh!`.
+    pub fn refresh_nav(&mut self, now: i64) -> Result<NavRefresh> {
+        let total_aum_usd = self.total_aum_usd()?;
+        // Without supply there is no NAV to publish.
+        let nav = total_aum_usd
+            .checked_mul(10u128.pow(NAV_DECIMALS as u32))
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(self.usdu_supply)
+            .unwrap_or(0);
+        self.nav = u64::try_from(nav).map_err(|_| ErrorCode::MathOverflow)?;
+
+        Ok(NavRefresh {
+            peg: self.check_nav_peg(now),
+            collateral_crossings: self.refresh_collateral(total_aum_usd)?,
+        })
+    }
+
+    fn check_nav_peg(&mut self, now: i64) -> NavPegCheck {
+        if self.usdu_supply == 0 {
+            // No NAV is published without supply
+            return NavPegCheck::Within;
+        }
+        let one = 10i128.pow(NAV_DECIMALS as u32);
+        // At most u64::MAX / 100 for a u64 NAV, so it fits an i64
+        let deviation_bps = (self.nav as i128 - one) * BPS_DENOMINATOR as i128 / one;
+        self.nav_deviation_bps = deviation_bps as i64;
+
+        let magnitude_bps = deviation_bps.unsigned_abs() as u64;
+        if self.nav_peg_band_bps == 0 || magnitude_bps <= self.nav_peg_band_bps as u64 {
+            return match std::mem::take(&mut self.nav_off_peg_since) {
+                0 => NavPegCheck::Within,
+                off_peg_since => NavPegCheck::Restored { off_peg_since },
+            };
+        }
+        if self.nav_off_peg_since == 0 {
+            self.nav_off_peg_since = now;
+        }
+        NavPegCheck::OffPeg {
+            direction: if deviation_bps > 0 {
+                NavPegDirection::Above
+            } else {
+                NavPegDirection::Below
+            },
+            deviation_bps: magnitude_bps,
+        }
+    }
+
+    fn refresh_collateral(
+        &mut self,
+        total_aum_usd: u128,
+    ) -> Result<Vec<CollateralThresholdCrossing>> {
+        self.collateral_surplus_usd = i128::try_from(total_aum_usd)
+            .ok()
+            .zip(i128::try_from(self.usdu_supply).ok())
+            .and_then(|(aum, supply)| aum.checked_sub(supply))
+            .ok_or(ErrorCode::MathOverflow)?;
+        if self.usdu_supply == 0 {
+            // Nothing to back, so no threshold is crossed either way
+            self.collateral_ratio_bps = 0;
+            return Ok(Vec::new());
+        }
+        let ratio_bps = self.collateral_ratio_bps()?;
+        self.collateral_ratio_bps = ratio_bps;
+
+        let mut crossings = Vec::new();
+        for (i, threshold_bps) in COLLATERAL_RATIO_THRESHOLDS_BPS.into_iter().enumerate() {
+            let bit = 1 << i;
+            let is_below = ratio_bps < threshold_bps;
+            if is_below != (self.collateral_breaches & bit != 0) {
+                self.collateral_breaches ^= bit;
+                crossings.push(CollateralThresholdCrossing {
+                    threshold_bps,
+                    is_below,
+                });
+            }
+        }
+        Ok(crossings)
+    }
+
+    /// Total AUM over USDU supply, in basis points, computed from the current fields rather
+    /// than read from `collateral_ratio_bps`.
+    pub fn collateral_ratio_bps(&self) -> Result<u64> {
+        require!(self.usdu_supply != 0, ErrorCode::NavUnavailable);
+        let ratio = self
+            .total_aum_usd()?
+            .checked_mul(BPS_DENOMINATOR as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            / self.usdu_supply;
+        Ok(u64::try_from(ratio).unwrap_or(u64::MAX))
+    }
+
+    /// When the oldest input of the NAV was observed: the reported AUM and, while asset tables
+    /// are registered, the last on-chain valuation round.
+    pub fn nav_as_of(&self) -> i64 {
+        if self.asset_count > 0 {
+            self.aum_as_of.min(self.onchain_aum_timestamp)
+        } else {
+            self.aum_as_of
+        }
+    }
+
+    pub fn nav_status(&self, now: i64) -> NavStatus {
+        if self.is_paused {
+            NavStatus::Paused
+        } else if self.last_updated_timestamp == 0 || self.usdu_supply == 0 {
+            NavStatus::Unavailable
+        } else if now.saturating_sub(self.nav_as_of()) > self.max_aum_age {
+            // Aged from when the inputs were observed, not when the update landed
+            NavStatus::Stale
+        } else {
+            NavStatus::Active
+        }
+    }
+}
+
+/// Seed scoping the config, operator and asset lookup table PDAs to a fund. Fund 0 keeps the
+/// addresses those accounts had before funds were introduced, so its seed is empty.
+pub fn fund_seed(fund_id: u16) -> Vec<u8> {
+    if fund_id == 0 {
+        Vec::new()
+    } else {
+        fund_id.to_le_bytes().to_vec()
+    }
+}
+
+/// URI of a reserves report, stored at its maximum length so the config fields after it keep
+/// fixed offsets.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub struct ReservesUri {
+    len: u8,
+    bytes: [u8; MAX_RESERVES_URI_LEN],
+}
+
+impl ReservesUri {
+    pub fn new(uri: &str) -> Result<Self> {
+        require!(
+            uri.len() <= MAX_RESERVES_URI_LEN,
+            ErrorCode::ReservesUriTooLong
+        );
+        let mut bytes = [0; MAX_RESERVES_URI_LEN];
+        bytes[..uri.len()].copy_from_slice(uri.as_bytes());
+        Ok(ReservesUri {
+            len: uri.len() as u8,
+            bytes,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        let len = (self.len as usize).min(MAX_RESERVES_URI_LEN);
+        std::str::from_utf8(&self.bytes[..len]).unwrap_or_default()
+    }
+}
+
+impl Default for ReservesUri {
+    fn default() -> Self {
+        ReservesUri {
+            len: 0,
+            bytes: [0; MAX_RESERVES_URI_LEN],
+        }
+    }
+}
+
+impl std::fmt::Debug for ReservesUri {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+/// What a NAV refresh found, for the caller to report as events.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct NavRefresh {
+    pub peg: NavPegCheck,
+    pub collateral_crossings: Vec<CollateralThresholdCrossing>,
+}
+
+/// Where the refreshed NAV stands against `nav_peg_band_bps`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NavPegCheck {
+    Within,
+    /// Outside the band, by `deviation_bps` of 1.0
+    OffPeg {
+        direction: NavPegDirection,
+        deviation_bps: u64,
+    },
+    /// Back within the band after leaving it at `off_peg_since`
+    Restored {
+        off_peg_since: i64,
+    },
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NavPegDirection {
+    Above,
+    Below,
+}
+
+/// A collateral ratio threshold the ratio fell below or recovered to on a NAV refresh.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CollateralThresholdCrossing {
+    pub threshold_bps: u64,
+    pub is_below: bool,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NavStatus {
+    Active,
+    Stale,
+    Paused,
+    /// No AUM has been published yet, or USDU has no supply
+    Unavailable,
 }
 
 // Operator is part of the auth model, keep it here.
@@ -38,3 +347,171 @@ pub struct Operator {
 impl Operator {
     pub const LEN: usize = 8 + 32;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn refresh(config: &mut UnitasConfig, aum_usd: u128) -> Vec<(u64, bool)> {
+        config.aum_usd = aum_usd;
+        config
+            .refresh_nav(0)
+            .unwrap()
+            .collateral_crossings
+            .into_iter()
+            .map(|crossing| (crossing.threshold_bps, crossing.is_below))
+            .collect()
+    }
+
+    #[test]
+    fn reports_collateral_thresholds_as_they_are_crossed() {
+        let mut config = UnitasConfig {
+            usdu_supply: 1_000_000,
+            ..Default::default()
+        };
+
+        assert_eq!(refresh(&mut config, 1_050_000), vec![]);
+        assert_eq!(config.collateral_ratio_bps, 10_500);
+        assert_eq!(config.collateral_surplus_usd, 50_000);
+
+        assert_eq!(refresh(&mut config, 1_010_000), vec![(10_200, true)]);
+        assert_eq!(refresh(&mut config, 1_015_000), vec![]);
+        assert_eq!(refresh(&mut config, 990_000), vec![(10_000, true)]);
+        assert_eq!(config.collateral_ratio_bps, 9_900);
+        assert_eq!(config.collateral_surplus_usd, -10_000);
+        assert_eq!(config.collateral_breaches, 0b11);
+
+        assert_eq!(
+            refresh(&mut config, 1_030_000),
+            vec![(10_000, false), (10_200, false)]
+        );
+        assert_eq!(config.collateral_breaches, 0);
+    }
+
+    #[test]
+    fn reports_nav_outside_its_peg_band() {
+        let mut config = UnitasConfig {
+            usdu_supply: 1_000_000,
+            nav_peg_band_bps: 50,
+            ..Default::default()
+        };
+        let peg_at = |config: &mut UnitasConfig, aum_usd: u128, now: i64| {
+            config.aum_usd = aum_usd;
+            config.refresh_nav(now).unwrap().peg
+        };
+
+        assert_eq!(peg_at(&mut config, 1_004_000, 100), NavPegCheck::Within);
+        assert_eq!(config.nav_deviation_bps, 40);
+
+        assert_eq!(
+            peg_at(&mut config, 993_000, 200),
+            NavPegCheck::OffPeg {
+                direction: NavPegDirection::Below,
+                deviation_bps: 70,
+            }
+        );
+        assert_eq!(config.nav_deviation_bps, -70);
+        assert_eq!(config.nav_off_peg_since, 200);
+        assert_eq!(
+            peg_at(&mut config, 1_020_000, 300),
+            NavPegCheck::OffPeg {
+                direction: NavPegDirection::Above,
+                deviation_bps: 200,
+            }
+        );
+        assert_eq!(config.nav_off_peg_since, 200);
+
+        assert_eq!(
+            peg_at(&mut config, 1_000_000, 400),
+            NavPegCheck::Restored { off_peg_since: 200 }
+        );
+        assert_eq!(config.nav_deviation_bps, 0);
+        assert_eq!(config.nav_off_peg_since, 0);
+
+        config.nav_peg_band_bps = 0;
+        assert_eq!(peg_at(&mut config, 900_000, 500), NavPegCheck::Within);
+        assert_eq!(config.nav_deviation_bps, -1_000);
+    }
+
+    #[test]
+    fn nav_ages_from_the_aum_observation() {
+        let config = UnitasConfig {
+            usdu_supply: 1_000_000,
+            last_updated_timestamp: 1_000,
+            aum_as_of: 400,
+            max_aum_age: 500,
+            ..Default::default()
+        };
+        assert_eq!(config.nav_status(900), NavStatus::Active);
+        assert_eq!(config.nav_status(1_000), NavStatus::Stale);
+    }
+
+    #[test]
+    fn nav_ages_from_the_last_valuation_round_while_tables_are_registered() {
+        let mut config = UnitasConfig {
+            usdu_supply: 1_000_000,
+            last_updated_timestamp: 1_000,
+            aum_as_of: 900,
+            onchain_aum_timestamp: 300,
+            max_aum_age: 500,
+            ..Default::default()
+        };
+        // Without tables the on-chain AUM is unused and its age doesn't count
+        assert_eq!(config.nav_status(1_000), NavStatus::Active);
+
+        config.asset_count = 2;
+        assert_eq!(config.nav_as_of(), 300);
+        assert_eq!(config.nav_status(800), NavStatus::Active);
+        assert_eq!(config.nav_status(1_000), NavStatus::Stale);
+    }
+
+    #[test]
+    fn fund_zero_keeps_the_legacy_config_address() {
+        let address = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &crate::ID).0;
+        let legacy = address(&[crate::ADMIN_CONFIG_SEED.as_bytes()]);
+        assert_eq!(
+            address(&[crate::ADMIN_CONFIG_SEED.as_bytes(), &fund_seed(0)]),
+            legacy
+        );
+        assert_ne!(
+            address(&[crate::ADMIN_CONFIG_SEED.as_bytes(), &fund_seed(1)]),
+            legacy
+        );
+    }
+
+    #[test]
+    fn reserves_uri_has_a_fixed_size() {
+        let uri = ReservesUri::new("ipfs://reserves").unwrap();
+        assert_eq!(uri.as_str(), "ipfs://reserves");
+        assert_eq!(uri.try_to_vec().unwrap().len(), 1 + MAX_RESERVES_URI_LEN);
+        assert_eq!(
+            ReservesUri::new(&"x".repeat(MAX_RESERVES_URI_LEN))
+                .unwrap()
+                .as_str()
+                .len(),
+            MAX_RESERVES_URI_LEN
+        );
+        assert_eq!(
+            ReservesUri::new(&"x".repeat(MAX_RESERVES_URI_LEN + 1)).unwrap_err(),
+            error!(ErrorCode::ReservesUriTooLong)
+        );
+    }
+
+    #[test]
+    fn keeps_breaches_while_there_is_no_supply() {
+        let mut config = UnitasConfig {
+            usdu_supply: 1_000_000,
+            ..Default::default()
+        };
+        assert_eq!(
+            refresh(&mut config, 900_000),
+            vec![(10_000, true), (10_200, true)]
+        );
+
+        config.usdu_supply = 0;
+        assert_eq!(refresh(&mut config, 900_000), vec![]);
+        assert_eq!(config.collateral_ratio_bps, 0);
+        assert_eq!(config.collateral_surplus_usd, 900_000);
+        assert_eq!(config.collateral_breaches, 0b11);
+    }
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use crate::CUSTODIAN_ATTESTATION_DOMAIN;
+
+pub const ETH_ADDRESS_LEN: usize = 20;
+
+const ETH_SIGNED_MESSAGE_PREFIX: &[u8] = b"\x19Ethereum Signed Message:\n";
+
+// A custodian that reports off-chain balances through Ethereum-style signed statements.
+#[account]
+#[derive(Default)]
+pub struct Custodian {
+    pub eth_address: [u8; ETH_ADDRESS_LEN],
+    pub attested_balance_usd: u128,
+    pub attested_timestamp: i64,
+}
+
+impl Custodian {
+    pub const LEN: usize = 8 + // discriminator
+        20 + // eth_address
+        16 + // attested_balance_usd
+        8; // attested_timestamp
+
+    /// Whether the attested balance is older than `max_age` and should no longer back the AUM.
+    pub fn is_expired(&self, now: i64, max_age: i64) -> bool {
+        self.attested_balance_usd != 0 && now.saturating_sub(self.attested_timestamp) > max_age
+    }
+
+    /// Builds the message a custodian signs with `personal_sign`.
+    ///
+    /// The body is `domain || config || eth_address || balance_usd (u128 BE) || timestamp (i64 BE)`,
+    /// wrapped in the EIP-191 prefix so the secp256k1 program's keccak256 over the message yields
+    /// the same digest as an Ethereum wallet signature.
+    pub fn attestation_message(
+        config: &Pubkey,
+        eth_address: &[u8; ETH_ADDRESS_LEN],
+        balance_usd: u128,
+        timestamp: i64,
+    ) -> Vec<u8> {
+        let mut body = Vec::with_capacity(CUSTODIAN_ATTESTATION_DOMAIN.len() + 32 + 20 + 16 + 8);
+        body.extend_from_slice(CUSTODIAN_ATTESTATION_DOMAIN);
+        body.extend_from_slice(config.as_ref());
+        body.extend_from_slice(eth_address);
+        body.extend_from_slice(&balance_usd.to_be_bytes());
+        body.extend_from_slice(&timestamp.to_be_bytes());
+
+        let body_len = body.len().to_string();
+        let mut message =
+            Vec::with_capacity(ETH_SIGNED_MESSAGE_PREFIX.len() + body_len.len() + body.len());
+        message.extend_from_slice(ETH_SIGNED_MESSAGE_PREFIX);
+        message.extend_from_slice(body_len.as_bytes());
+        message.extend_from_slice(&body);
+        message
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn attestation_message_is_eip191_wrapped() {
+        let config = Pubkey::new_from_array([0x11; 32]);
+        let message = Custodian::attestation_message(
+            &config,
+            &[0x22; ETH_ADDRESS_LEN],
+            0x0102_0304,
+            1_700_000_000,
+        );
+
+        let expected = [
+            b"\x19Ethereum Signed Message:\n110".as_slice(),
+            b"unitas-oracle:custodian-balance:v1",
+            &[0x11; 32],
+            &[0x22; 20],
+            &[0; 12],
+            &[0x01, 0x02, 0x03, 0x04],
+            &[0x00, 0x00, 0x00, 0x00, 0x65, 0x53, 0xf1, 0x00],
+        ]
+        .concat();
+        assert_eq!(message, expected);
+    }
+
+    #[test]
+    fn attested_balance_expires_after_max_age() {
+        let mut custodian = Custodian {
+            eth_address: [0x22; ETH_ADDRESS_LEN],
+            attested_balance_usd: 5_000_000,
+            attested_timestamp: 1_000,
+        };
+        assert!(!custodian.is_expired(1_500, 500));
+        assert!(custodian.is_expired(1_501, 500));
+
+        // Nothing left to expire
+        custodian.attested_balance_usd = 0;
+        assert!(!custodian.is_expired(1_501, 500));
+    }
+}
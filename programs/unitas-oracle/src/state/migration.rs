@@ -0,0 +1,301 @@
+use anchor_lang::error::ErrorCode as AnchorErrorCode;
+use anchor_lang::prelude::*;
+use bytemuck::{Pod, Zeroable};
+
+use crate::error::ErrorCode;
+use crate::state::{
+    AssetKind, AssetLookupTable, PricingMode, UnitasConfig, MAX_ACCOUNTS_PER_ASSET, MAX_PRICE_LEGS,
+};
+use crate::{ASSET_LOOKUP_TABLE_VERSION, CONFIG_VERSION, DEFAULT_MAX_AUM_AGE};
+
+// Layouts of the first deployed program, before accounts carried a version. Accounts are
+// allocated with the exact `LEN` of their layout, so the data length identifies which one an
+// account uses.
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct UnitasConfigV0 {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub aum_usd: u128,
+    pub last_updated_timestamp: i64,
+    pub usdu_config: Pubkey,
+}
+
+impl UnitasConfigV0 {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // admin
+        32 + // pending_admin
+        16 + // aum_usd
+        8 +  // last_updated_timestamp
+        32; // usdu_config
+}
+
+impl From<UnitasConfigV0> for UnitasConfig {
+    fn from(legacy: UnitasConfigV0) -> Self {
+        UnitasConfig {
+            version: CONFIG_VERSION,
+            // The deployed config sits at the fund 0 address, see `fund_seed`
+            fund_id: 0,
+            admin: legacy.admin,
+            pending_admin: legacy.pending_admin,
+            aum_usd: legacy.aum_usd,
+            last_updated_timestamp: legacy.last_updated_timestamp,
+            // The AUM carried no observation time, so it is aged from when it was written
+            aum_as_of: legacy.last_updated_timestamp,
+            usdu_config: legacy.usdu_config,
+            max_aum_age: DEFAULT_MAX_AUM_AGE,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Clone, Copy, Pod, Zeroable)]
+#[repr(C)]
+pub struct AssetLookupTableV0 {
+    pub asset_mint: Pubkey,
+    pub oracle_account: Pubkey,
+    pub token_account_owners: [Pubkey; MAX_ACCOUNTS_PER_ASSET],
+    pub token_account_owners_len: u32,
+    pub decimals: u8,
+    pub paddings: [u8; 3],
+}
+
+impl AssetLookupTableV0 {
+    pub const LEN: usize = 8 + std::mem::size_of::<AssetLookupTableV0>();
+
+    /// Upgrades the table to the current layout, registering it under `config`.
+    fn upgrade(self, config: Pubkey) -> AssetLookupTable {
+        AssetLookupTable {
+            config,
+            asset_mint: self.asset_mint,
+            oracle_account: self.oracle_account,
+            token_account_owners: self.token_account_owners,
+            token_account_owners_len: self.token_account_owners_len,
+            decimals: self.decimals,
+            version: ASSET_LOOKUP_TABLE_VERSION,
+            paddings: [0; 2],
+            last_price: 0,
+            last_total_amount: 0,
+            last_usd_value: 0,
+            last_valued_at: 0,
+            last_price_exponent: 0,
+            last_valuation_source: 0,
+            valuation_paddings: [0; 3],
+            last_accumulated_round: 0,
+            // The first program only tracked SPL tokens
+            asset_kind: AssetKind::SplToken as u8,
+            kind_paddings: [0; 7],
+            stake_pool: Pubkey::default(),
+            price_legs: [Pubkey::default(); MAX_PRICE_LEGS - 1],
+            price_legs_len: 0,
+            price_leg_divide_mask: 0,
+            price_path_paddings: [0; 6],
+            peg_band_bps: 0,
+            is_depegged: 0,
+            pricing_mode: PricingMode::Spot as u8,
+            peg_paddings: [0; 4],
+            price_feed_ids_hash: [0; 32],
+            reserved: [0; 7],
+        }
+    }
+}
+
+impl UnitasConfig {
+    /// Decodes the config in `data` (including the discriminator) from an older layout and
+    /// returns it upgraded to the current one.
+    pub fn migrate(data: &[u8]) -> Result<UnitasConfig> {
+        require!(
+            data.starts_with(UnitasConfig::DISCRIMINATOR),
+            AnchorErrorCode::AccountDiscriminatorMismatch
+        );
+
+        match data.len() {
+            UnitasConfigV0::LEN => {
+                let legacy = UnitasConfigV0::deserialize(&mut &data[8..])
+                    .map_err(|_| error!(AnchorErrorCode::AccountDidNotDeserialize))?;
+                Ok(legacy.into())
+            }
+            UnitasConfig::LEN => err!(ErrorCode::AccountAlreadyMigrated),
+            _ => err!(ErrorCode::UnknownAccountLayout),
+        }
+    }
+}
+
+impl AssetLookupTable {
+    /// Decodes the asset lookup table in `data` (including the discriminator) from an older
+    /// layout and returns it upgraded to the current one, registered under `config`.
+    pub fn migrate(data: &[u8], config: Pubkey) -> Result<AssetLookupTable> {
+        require!(
+            data.starts_with(AssetLookupTable::DISCRIMINATOR),
+            AnchorErrorCode::AccountDiscriminatorMismatch
+        );
+
+        match data.len() {
+            AssetLookupTableV0::LEN => {
+                let legacy = bytemuck::pod_read_unaligned::<AssetLookupTableV0>(&data[8..]);
+                Ok(legacy.upgrade(config))
+            }
+            AssetLookupTable::LEN => err!(ErrorCode::AccountAlreadyMigrated),
+            _ => err!(ErrorCode::UnknownAccountLayout),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ADMIN: Pubkey = Pubkey::new_from_array([1; 32]);
+    const PENDING_ADMIN: Pubkey = Pubkey::new_from_array([2; 32]);
+    const USDU_CONFIG: Pubkey = Pubkey::new_from_array([3; 32]);
+    const CONFIG: Pubkey = Pubkey::new_from_array([4; 32]);
+    const ASSET_MINT: Pubkey = Pubkey::new_from_array([5; 32]);
+    const ORACLE: Pubkey = Pubkey::new_from_array([6; 32]);
+    const OWNERS: [Pubkey; 2] = [
+        Pubkey::new_from_array([7; 32]),
+        Pubkey::new_from_array([8; 32]),
+    ];
+
+    /// A config as the first deployed program wrote it: discriminator, `admin`,
+    /// `pending_admin`, `aum_usd`, `last_updated_timestamp` and `usdu_config`.
+    fn legacy_config_fixture() -> Vec<u8> {
+        let mut data = UnitasConfig::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(ADMIN.as_ref());
+        data.extend_from_slice(PENDING_ADMIN.as_ref());
+        data.extend_from_slice(&1_250_000_000_000u128.to_le_bytes());
+        data.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+        data.extend_from_slice(USDU_CONFIG.as_ref());
+        assert_eq!(data.len(), 128);
+        data
+    }
+
+    /// An asset lookup table as the first deployed program wrote it, with two owners:
+    /// discriminator, `asset_mint`, `oracle_account`, 16 owner slots, `token_account_owners_len`,
+    /// `decimals` and 3 bytes of padding.
+    fn legacy_asset_lookup_table_fixture() -> Vec<u8> {
+        let mut data = AssetLookupTable::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(ASSET_MINT.as_ref());
+        data.extend_from_slice(ORACLE.as_ref());
+        for i in 0..MAX_ACCOUNTS_PER_ASSET {
+            let owner = OWNERS.get(i).copied().unwrap_or_default();
+            data.extend_from_slice(owner.as_ref());
+        }
+        data.extend_from_slice(&(OWNERS.len() as u32).to_le_bytes());
+        data.push(9);
+        data.extend_from_slice(&[0; 3]);
+        assert_eq!(data.len(), 592);
+        data
+    }
+
+    #[test]
+    fn legacy_layouts_match_the_deployed_sizes() {
+        assert_eq!(UnitasConfigV0::LEN, 128);
+        assert_eq!(AssetLookupTableV0::LEN, 592);
+    }
+
+    #[test]
+    fn migrates_legacy_config() {
+        let config = UnitasConfig::migrate(&legacy_config_fixture()).unwrap();
+
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert_eq!(config.fund_id, 0);
+        assert_eq!(config.admin, ADMIN);
+        assert_eq!(config.pending_admin, PENDING_ADMIN);
+        assert_eq!(config.aum_usd, 1_250_000_000_000);
+        assert_eq!(config.last_updated_timestamp, 1_700_000_000);
+        assert_eq!(config.aum_as_of, 1_700_000_000);
+        assert_eq!(config.usdu_config, USDU_CONFIG);
+        assert_eq!(config.max_aum_age, DEFAULT_MAX_AUM_AGE);
+        assert_eq!(config.aum_sequence, 0);
+        assert_eq!(config.attested_aum_usd, 0);
+        assert_eq!(config.usdu_supply, 0);
+        assert_eq!(config.nav, 0);
+        assert_eq!(config.reserves_uri.as_str(), "");
+        assert!(!config.is_paused);
+        assert_eq!(config.onchain_aum_usd, 0);
+        assert_eq!(config.asset_count, 0);
+        assert_eq!(config.collateral_breaches, 0);
+        assert_eq!(config.nav_peg_band_bps, 0);
+        assert_eq!(config.reserved, [0; 12]);
+    }
+
+    #[test]
+    fn migrated_config_fits_and_round_trips() {
+        let config = UnitasConfig::migrate(&legacy_config_fixture()).unwrap();
+
+        let mut data = Vec::with_capacity(UnitasConfig::LEN);
+        config.try_serialize(&mut data).unwrap();
+        assert_eq!(data.len(), UnitasConfig::LEN);
+
+        let decoded = UnitasConfig::try_deserialize(&mut &data[..]).unwrap();
+        assert_eq!(decoded.version, CONFIG_VERSION);
+        assert_eq!(decoded.admin, ADMIN);
+        assert_eq!(decoded.usdu_config, USDU_CONFIG);
+        assert_eq!(decoded.max_aum_age, DEFAULT_MAX_AUM_AGE);
+
+        assert_eq!(
+            UnitasConfig::migrate(&data).unwrap_err(),
+            error!(ErrorCode::AccountAlreadyMigrated)
+        );
+    }
+
+    #[test]
+    fn rejects_config_with_unknown_layout() {
+        let mut data = legacy_config_fixture();
+        data.truncate(UnitasConfigV0::LEN - 1);
+        assert_eq!(
+            UnitasConfig::migrate(&data).unwrap_err(),
+            error!(ErrorCode::UnknownAccountLayout)
+        );
+    }
+
+    #[test]
+    fn rejects_config_with_wrong_discriminator() {
+        let mut data = legacy_config_fixture();
+        data[..8].copy_from_slice(AssetLookupTable::DISCRIMINATOR);
+        assert_eq!(
+            UnitasConfig::migrate(&data).unwrap_err(),
+            error!(AnchorErrorCode::AccountDiscriminatorMismatch)
+        );
+    }
+
+    #[test]
+    fn migrates_legacy_asset_lookup_table() {
+        let data = legacy_asset_lookup_table_fixture();
+        let table = AssetLookupTable::migrate(&data, CONFIG).unwrap();
+
+        assert_eq!(table.version, ASSET_LOOKUP_TABLE_VERSION);
+        assert_eq!(table.config, CONFIG);
+        assert_eq!(table.asset_mint, ASSET_MINT);
+        assert_eq!(table.oracle_account, ORACLE);
+        assert_eq!(table.token_account_owners[..2], OWNERS);
+        assert_eq!(table.token_account_owners[2], Pubkey::default());
+        assert_eq!(table.token_account_owners_len, 2);
+        assert_eq!(table.decimals, 9);
+        assert_eq!(table.last_valued_at, 0);
+        assert_eq!(table.asset_kind().unwrap(), AssetKind::SplToken);
+        assert_eq!(table.price_path().len(), 1);
+        assert_eq!(table.pricing_mode().unwrap(), PricingMode::Spot);
+        // Accumulating needs the feed ids, set along with the price path
+        assert_eq!(table.price_feed_ids_hash, [0; 32]);
+        assert_eq!(table.reserved, [0; 7]);
+
+        let mut migrated = AssetLookupTable::DISCRIMINATOR.to_vec();
+        migrated.extend_from_slice(bytemuck::bytes_of(&table));
+        assert_eq!(migrated.len(), AssetLookupTable::LEN);
+        assert_eq!(
+            AssetLookupTable::migrate(&migrated, CONFIG).unwrap_err(),
+            error!(ErrorCode::AccountAlreadyMigrated)
+        );
+    }
+
+    #[test]
+    fn rejects_asset_lookup_table_with_wrong_discriminator() {
+        let mut data = legacy_asset_lookup_table_fixture();
+        data[..8].copy_from_slice(UnitasConfig::DISCRIMINATOR);
+        assert_eq!(
+            AssetLookupTable::migrate(&data, CONFIG).unwrap_err(),
+            error!(AnchorErrorCode::AccountDiscriminatorMismatch)
+        );
+    }
+}
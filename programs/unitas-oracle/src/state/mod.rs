@@ -1,5 +1,21 @@
+pub mod account_label;
 pub mod asset;
 pub mod config;
+pub mod custodian;
+pub mod migration;
+pub mod price_feed;
+pub mod price_path;
+pub mod stake_pool;
+pub mod usdu;
+pub mod valuation;
 
+pub use account_label::*;
 pub use asset::*;
 pub use config::*;
+pub use custodian::*;
+pub use migration::*;
+pub use price_feed::*;
+pub use price_path::*;
+pub use stake_pool::*;
+pub use usdu::*;
+pub use valuation::*;
@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::state::UnitasConfig;
+use crate::{MAX_ORACLE_PRICE_AGE, NAV_DECIMALS, NAV_EMA_WINDOW, PYTH_RECEIVER_PROGRAM_ID};
+
+/// USDU/USD price account laid out exactly like the Pyth receiver's `PriceUpdateV2`, so
+/// integrations that already decode Pyth price updates can read the NAV without an adapter.
+/// The struct name is part of the layout: it determines the Anchor discriminator.
+#[account]
+#[derive(Debug)]
+pub struct PriceUpdateV2 {
+    pub write_authority: Pubkey,
+    pub verification_level: VerificationLevel,
+    pub price_message: PriceFeedMessage,
+    pub posted_slot: u64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerificationLevel {
+    Partial { num_signatures: u8 },
+    Full,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PriceFeedMessage {
+    pub feed_id: [u8; 32],
+    pub price: i64,
+    pub conf: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+    pub prev_publish_time: i64,
+    pub ema_price: i64,
+    pub ema_conf: u64,
+}
+
+impl PriceUpdateV2 {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // write_authority
+        2 +  // verification_level
+        32 + // feed_id
+        8 +  // price
+        8 +  // conf
+        4 +  // exponent
+        8 +  // publish_time
+        8 +  // prev_publish_time
+        8 +  // ema_price
+        8 +  // ema_conf
+        8; // posted_slot
+
+    /// Reads a fully verified, fresh price from a Pyth receiver `PriceUpdateV2` account.
+    pub fn load_pyth_price(account: &AccountInfo, now: i64) -> Result<PriceFeedMessage> {
+        require_keys_eq!(
+            *account.owner,
+            PYTH_RECEIVER_PROGRAM_ID,
+            ErrorCode::InvalidOracle
+        );
+        let update = PriceUpdateV2::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+        require!(
+            update.verification_level == VerificationLevel::Full,
+            ErrorCode::InvalidOracle
+        );
+        let message = update.price_message;
+        require!(
+            now.saturating_sub(message.publish_time) <= MAX_ORACLE_PRICE_AGE,
+            ErrorCode::StaleOraclePrice
+        );
+        Ok(message)
+    }
+
+    /// Publishes the config's NAV to the USDU price feed, which must be passed once
+    /// `init_price_feed` has created it so that a NAV change can't leave the feed behind.
+    pub fn republish_nav(
+        price_feed: Option<&mut PriceUpdateV2>,
+        config: &UnitasConfig,
+        clock: &Clock,
+    ) -> Result<()> {
+        match price_feed {
+            Some(price_feed) => price_feed.publish_nav(config, clock),
+            None => {
+                require!(!config.has_price_feed, ErrorCode::PriceFeedRequired);
+                Ok(())
+            }
+        }
+    }
+
+    /// Publishes the config's NAV. `publish_time` is when the NAV changed, so every republish
+    /// is newer than the one before even when the inputs behind it were observed earlier,
+    /// e.g. an attestation refreshing the NAV on top of an older AUM.
+    pub fn publish_nav(&mut self, config: &UnitasConfig, clock: &Clock) -> Result<()> {
+        let price = i64::try_from(config.nav).map_err(|_| ErrorCode::MathOverflow)?;
+        let message = &mut self.price_message;
+        // The first published price seeds the EMA.
+        let ema_price = if message.ema_price == 0 {
+            price
+        } else {
+            let window = NAV_EMA_WINDOW as i128;
+            ((message.ema_price as i128 * (window - 1) + price as i128) / window) as i64
+        };
+
+        message.prev_publish_time = message.publish_time;
+        message.publish_time = clock.unix_timestamp;
+        message.price = price;
+        message.conf = 0;
+        message.exponent = -(NAV_DECIMALS as i32);
+        message.ema_price = ema_price;
+        message.ema_conf = 0;
+        self.posted_slot = clock.slot;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clock(unix_timestamp: i64) -> Clock {
+        Clock {
+            slot: unix_timestamp as u64 * 2,
+            unix_timestamp,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn feed_is_required_once_created() {
+        let mut config = UnitasConfig::default();
+        assert!(PriceUpdateV2::republish_nav(None, &config, &clock(1)).is_ok());
+
+        config.has_price_feed = true;
+        assert_eq!(
+            PriceUpdateV2::republish_nav(None, &config, &clock(1)).unwrap_err(),
+            error!(ErrorCode::PriceFeedRequired)
+        );
+    }
+
+    #[test]
+    fn republishes_with_the_time_of_the_nav_change() {
+        let mut feed = PriceUpdateV2 {
+            write_authority: Pubkey::default(),
+            verification_level: VerificationLevel::Full,
+            price_message: PriceFeedMessage::default(),
+            posted_slot: 0,
+        };
+        let mut config = UnitasConfig {
+            nav: 1_000_000,
+            aum_as_of: 50,
+            ..Default::default()
+        };
+        feed.publish_nav(&config, &clock(100)).unwrap();
+        assert_eq!(feed.price_message.publish_time, 100);
+        assert_eq!(feed.posted_slot, 200);
+
+        // An attestation moves the NAV without a newer AUM observation
+        config.nav = 1_010_000;
+        feed.publish_nav(&config, &clock(130)).unwrap();
+        assert_eq!(feed.price_message.publish_time, 130);
+        assert_eq!(feed.price_message.prev_publish_time, 100);
+        assert_eq!(feed.price_message.price, 1_010_000);
+    }
+}
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hashv;
+
+use crate::error::ErrorCode;
+use crate::state::PriceFeedMessage;
+
+/// Maximum number of oracle legs in an asset's price path, `oracle_account` included.
+pub const MAX_PRICE_LEGS: usize = 3;
+
+// Composed prices keep at most 18 significant digits, so the next leg can't overflow a u128.
+const MAX_MANTISSA: u128 = 1_000_000_000_000_000_000;
+const MAX_MANTISSA_DIGITS: i32 = 18;
+
+/// One oracle of a price path, e.g. asset/SOL followed by SOL/USD.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PriceLeg {
+    pub oracle: Pubkey,
+    /// Divide by this leg's price instead of multiplying, for feeds quoted the other way round
+    pub divide: bool,
+}
+
+/// Commitment to the Pyth feed ids a price path's oracles must publish, leg by leg, which
+/// tables store in place of the ids themselves.
+pub fn price_feed_ids_hash(feed_ids: &[[u8; 32]]) -> [u8; 32] {
+    let feed_ids: Vec<&[u8]> = feed_ids.iter().map(|feed_id| feed_id.as_slice()).collect();
+    hashv(&feed_ids).to_bytes()
+}
+
+/// Which of an oracle's prices an asset is valued at.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[repr(u8)]
+pub enum PricingMode {
+    #[default]
+    Spot,
+    Ema,
+    /// The lower of spot and EMA, leg by leg, so the composed price is the lowest either gives
+    Conservative,
+}
+
+impl PricingMode {
+    /// Price of `message` to use for a leg of a price path.
+    pub fn leg_price(&self, message: &PriceFeedMessage, divide: bool) -> i64 {
+        match self {
+            PricingMode::Spot => message.price,
+            PricingMode::Ema => message.ema_price,
+            // Dividing by the higher price lowers the result
+            PricingMode::Conservative if divide => message.price.max(message.ema_price),
+            PricingMode::Conservative => message.price.min(message.ema_price),
+        }
+    }
+}
+
+impl TryFrom<u8> for PricingMode {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(PricingMode::Spot),
+            1 => Ok(PricingMode::Ema),
+            2 => Ok(PricingMode::Conservative),
+            _ => err!(ErrorCode::UnknownPricingMode),
+        }
+    }
+}
+
+/// Chains `(price, exponent, divide)` quotes into one price and exponent.
+pub fn compose_price(quotes: impl IntoIterator<Item = (i64, i32, bool)>) -> Result<(i64, i32)> {
+    let mut mantissa: u128 = 1;
+    let mut exponent: i32 = 0;
+    for (price, price_exponent, divide) in quotes {
+        require!(price > 0, ErrorCode::InvalidOraclePrice);
+        if divide {
+            // Scale up first so the quotient keeps its precision
+            mantissa = mantissa
+                .checked_mul(MAX_MANTISSA)
+                .ok_or(ErrorCode::MathOverflow)?
+                / price as u128;
+            exponent -= MAX_MANTISSA_DIGITS + price_exponent;
+        } else {
+            mantissa = mantissa
+                .checked_mul(price as u128)
+                .ok_or(ErrorCode::MathOverflow)?;
+            exponent += price_exponent;
+        }
+        while mantissa > MAX_MANTISSA {
+            mantissa /= 10;
+            exponent += 1;
+        }
+    }
+    require!(mantissa > 0, ErrorCode::InvalidOraclePrice);
+    Ok((mantissa as i64, exponent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_leg_is_unchanged() {
+        assert_eq!(
+            compose_price([(425_000_000, -8, false)]).unwrap(),
+            (425_000_000, -8)
+        );
+    }
+
+    #[test]
+    fn multiplies_legs_with_their_exponents() {
+        // 0.02 SOL per token at $150 per SOL is $3
+        let (price, exponent) =
+            compose_price([(2_000_000, -8, false), (15_000_000_000, -8, false)]).unwrap();
+        assert_eq!((price, exponent), (30_000_000_000_000_000, -16));
+    }
+
+    #[test]
+    fn divides_by_inverted_legs() {
+        // $3,000 per ETH divided by $150 per SOL is 20 SOL per ETH
+        let (price, exponent) =
+            compose_price([(300_000_000_000, -8, false), (15_000_000_000, -8, true)]).unwrap();
+        assert_eq!((price, exponent), (200_000_000_000_000_000, -16));
+    }
+
+    #[test]
+    fn conservative_mode_takes_the_lower_valuation() {
+        let message = PriceFeedMessage {
+            price: 101,
+            ema_price: 99,
+            ..Default::default()
+        };
+        assert_eq!(PricingMode::Spot.leg_price(&message, false), 101);
+        assert_eq!(PricingMode::Ema.leg_price(&message, false), 99);
+        assert_eq!(PricingMode::Conservative.leg_price(&message, false), 99);
+        assert_eq!(PricingMode::Conservative.leg_price(&message, true), 101);
+        assert_eq!(
+            PricingMode::try_from(3).unwrap_err(),
+            error!(ErrorCode::UnknownPricingMode)
+        );
+    }
+
+    #[test]
+    fn rejects_non_positive_leg() {
+        assert_eq!(
+            compose_price([(425_000_000, -8, false), (0, -8, true)]).unwrap_err(),
+            error!(ErrorCode::InvalidOraclePrice)
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::SPL_STAKE_POOL_PROGRAM_ID;
+
+/// Exchange rate of an SPL stake pool, read from the pool account.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StakePoolRate {
+    pub pool_mint: Pubkey,
+    pub total_lamports: u64,
+    pub pool_token_supply: u64,
+    pub last_update_epoch: u64,
+}
+
+impl StakePoolRate {
+    // `spl_stake_pool::state::StakePool` is Borsh encoded with fixed-size fields up to
+    // `last_update_epoch`, so the fields are read in place.
+    const ACCOUNT_TYPE_STAKE_POOL: u8 = 1;
+    const POOL_MINT_OFFSET: usize = 162;
+    const TOTAL_LAMPORTS_OFFSET: usize = 258;
+    const POOL_TOKEN_SUPPLY_OFFSET: usize = 266;
+    const LAST_UPDATE_EPOCH_OFFSET: usize = 274;
+
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        require!(
+            data.len() >= Self::LAST_UPDATE_EPOCH_OFFSET + 8
+                && data[0] == Self::ACCOUNT_TYPE_STAKE_POOL,
+            ErrorCode::InvalidStakePool
+        );
+        let read_u64 =
+            |offset: usize| u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap());
+        Ok(StakePoolRate {
+            pool_mint: Pubkey::try_from(&data[Self::POOL_MINT_OFFSET..Self::POOL_MINT_OFFSET + 32])
+                .unwrap(),
+            total_lamports: read_u64(Self::TOTAL_LAMPORTS_OFFSET),
+            pool_token_supply: read_u64(Self::POOL_TOKEN_SUPPLY_OFFSET),
+            last_update_epoch: read_u64(Self::LAST_UPDATE_EPOCH_OFFSET),
+        })
+    }
+
+    /// Reads the rate of the stake pool `account`, which must be owned by the SPL stake pool
+    /// program.
+    pub fn load(account: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(
+            *account.owner,
+            SPL_STAKE_POOL_PROGRAM_ID,
+            ErrorCode::InvalidStakePool
+        );
+        Self::parse(&account.try_borrow_data()?)
+    }
+
+    /// Prices a pool token in SOL/USD units: `sol_price * total_lamports / pool_token_supply`,
+    /// with the exponent of `sol_price`. Pool tokens share SOL's 9 decimals. The rate must have
+    /// been updated in `epoch`, since rewards are only credited by the epoch update.
+    pub fn pool_token_price(&self, sol_price: i64, epoch: u64) -> Result<i64> {
+        require_eq!(self.last_update_epoch, epoch, ErrorCode::StaleStakePool);
+        require!(sol_price > 0, ErrorCode::InvalidOraclePrice);
+        require!(self.pool_token_supply > 0, ErrorCode::InvalidStakePool);
+        let price = (sol_price as u128)
+            .checked_mul(self.total_lamports as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            / self.pool_token_supply as u128;
+        i64::try_from(price).map_err(|_| error!(ErrorCode::MathOverflow))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool_data(pool_mint: &Pubkey, total: u64, supply: u64, epoch: u64) -> Vec<u8> {
+        let mut data = vec![0u8; 611];
+        data[0] = StakePoolRate::ACCOUNT_TYPE_STAKE_POOL;
+        data[162..194].copy_from_slice(pool_mint.as_ref());
+        data[258..266].copy_from_slice(&total.to_le_bytes());
+        data[266..274].copy_from_slice(&supply.to_le_bytes());
+        data[274..282].copy_from_slice(&epoch.to_le_bytes());
+        data
+    }
+
+    #[test]
+    fn prices_pool_token_at_the_exchange_rate() {
+        let pool_mint = Pubkey::new_unique();
+        let rate = StakePoolRate::parse(&pool_data(&pool_mint, 1_150, 1_000, 700)).unwrap();
+        assert_eq!(rate.pool_mint, pool_mint);
+        // 1.15 SOL per pool token at $150.00000000
+        assert_eq!(
+            rate.pool_token_price(15_000_000_000, 700).unwrap(),
+            17_250_000_000
+        );
+    }
+
+    #[test]
+    fn rejects_rate_from_a_past_epoch() {
+        let rate =
+            StakePoolRate::parse(&pool_data(&Pubkey::new_unique(), 1_150, 1_000, 699)).unwrap();
+        assert_eq!(
+            rate.pool_token_price(15_000_000_000, 700).unwrap_err(),
+            error!(ErrorCode::StaleStakePool)
+        );
+    }
+
+    #[test]
+    fn rejects_other_accounts() {
+        let mut data = pool_data(&Pubkey::new_unique(), 1_150, 1_000, 700);
+        data[0] = 2; // validator list
+        assert_eq!(
+            StakePoolRate::parse(&data).unwrap_err(),
+            error!(ErrorCode::InvalidStakePool)
+        );
+        assert_eq!(
+            StakePoolRate::parse(&data[..200]).unwrap_err(),
+            error!(ErrorCode::InvalidStakePool)
+        );
+
+        let empty = StakePoolRate::parse(&pool_data(&Pubkey::new_unique(), 0, 0, 700)).unwrap();
+        assert_eq!(
+            empty.pool_token_price(15_000_000_000, 700).unwrap_err(),
+            error!(ErrorCode::InvalidStakePool)
+        );
+    }
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+
+// Leading fields of the USDU program's config account, read for the token supply.
+#[derive(AnchorDeserialize, Debug)]
+pub struct UsduConfig {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub access_registry: Pubkey,
+    pub bump: u8,
+    pub is_initialized: bool,
+    pub usdu_token: Pubkey,
+    pub usdu_token_bump: u8,
+    pub is_usdu_token_initialized: bool,
+    pub total_supply: u128,
+}
+
+impl UsduConfig {
+    /// Reads the total USDU supply. The caller must have checked the account address
+    /// against `UnitasConfig::usdu_config`.
+    pub fn read_total_supply(account: &AccountInfo) -> Result<u128> {
+        let data = account.try_borrow_data()?;
+        let mut body = data.get(8..).ok_or(ErrorCode::InvalidUsduConfig)?;
+        let usdu_config =
+            UsduConfig::deserialize(&mut body).map_err(|_| ErrorCode::InvalidUsduConfig)?;
+        Ok(usdu_config.total_supply)
+    }
+}
@@ -0,0 +1,180 @@
+use anchor_lang::prelude::*;
+
+use crate::error::ErrorCode;
+use crate::{MAX_VALUATION_ROUND_DURATION, NAV_DECIMALS};
+
+/// Accumulates the on-chain valuation of a config's asset tables across transactions.
+/// One account per config, reused round after round.
+#[account]
+#[derive(Default)]
+pub struct ValuationRound {
+    pub config: Pubkey,
+    // Incremented by every `begin_valuation`
+    pub round: u64,
+    pub started_at: i64,
+    // `UnitasConfig::asset_count` when the round began
+    pub asset_count: u32,
+    pub accumulated_count: u32,
+    pub total_usd_value: u128,
+    pub is_finalized: bool,
+}
+
+impl ValuationRound {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // config
+        8 +  // round
+        8 +  // started_at
+        4 +  // asset_count
+        4 +  // accumulated_count
+        16 + // total_usd_value
+        1; // is_finalized
+
+    /// Whether assets can still be accumulated into, or the round finalized, at `now`.
+    pub fn is_open(&self, now: i64) -> bool {
+        self.round != 0
+            && !self.is_finalized
+            && now.saturating_sub(self.started_at) <= MAX_VALUATION_ROUND_DURATION
+    }
+
+    pub fn begin(&mut self, config: Pubkey, asset_count: u32, now: i64) -> Result<()> {
+        require!(!self.is_open(now), ErrorCode::ValuationRoundInProgress);
+        self.config = config;
+        self.round = self.round.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        self.started_at = now;
+        self.asset_count = asset_count;
+        self.accumulated_count = 0;
+        self.total_usd_value = 0;
+        self.is_finalized = false;
+        Ok(())
+    }
+
+    pub fn accumulate(&mut self, usd_value: u64, now: i64) -> Result<()> {
+        require!(self.is_open(now), ErrorCode::ValuationRoundClosed);
+        require!(
+            self.accumulated_count < self.asset_count,
+            ErrorCode::ValuationRoundClosed
+        );
+        self.accumulated_count += 1;
+        self.total_usd_value = self
+            .total_usd_value
+            .checked_add(usd_value as u128)
+            .ok_or(ErrorCode::MathOverflow)?;
+        Ok(())
+    }
+
+    /// Closes the round once every asset registered under the config has been accumulated.
+    pub fn finalize(&mut self, asset_count: u32, now: i64) -> Result<()> {
+        require!(self.is_open(now), ErrorCode::ValuationRoundClosed);
+        require!(
+            self.asset_count == asset_count && self.accumulated_count == asset_count,
+            ErrorCode::ValuationIncomplete
+        );
+        self.is_finalized = true;
+        Ok(())
+    }
+}
+
+/// USD value, scaled to `NAV_DECIMALS`, of `amount` base units of a token with `decimals`
+/// priced at `price * 10^exponent` per whole token.
+pub fn usd_value(amount: u64, decimals: u8, price: i64, exponent: i32) -> Result<u64> {
+    require!(price > 0, ErrorCode::InvalidOraclePrice);
+    let raw = (amount as u128)
+        .checked_mul(price as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let scale = NAV_DECIMALS as i32 + exponent - decimals as i32;
+    let pow = 10u128
+        .checked_pow(scale.unsigned_abs())
+        .ok_or(ErrorCode::MathOverflow)?;
+    let value = if scale >= 0 {
+        raw.checked_mul(pow).ok_or(ErrorCode::MathOverflow)?
+    } else {
+        raw / pow
+    };
+    u64::try_from(value).map_err(|_| error!(ErrorCode::MathOverflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn values_token_in_aum_decimals() {
+        // 1,500 JLP (6 decimals) at $4.25 with a Pyth exponent of -8
+        assert_eq!(
+            usd_value(1_500_000_000, 6, 425_000_000, -8).unwrap(),
+            6_375_000_000
+        );
+        // 2 SOL-like tokens (9 decimals) at $150 with a positive exponent
+        assert_eq!(usd_value(2_000_000_000, 9, 15, 1).unwrap(), 300_000_000);
+        // Dust below the AUM precision rounds down
+        assert_eq!(usd_value(1, 9, 100_000_000, -8).unwrap(), 0);
+    }
+
+    #[test]
+    fn rejects_non_positive_price() {
+        assert_eq!(
+            usd_value(1, 6, 0, -8).unwrap_err(),
+            error!(ErrorCode::InvalidOraclePrice)
+        );
+        assert_eq!(
+            usd_value(1, 6, -1, -8).unwrap_err(),
+            error!(ErrorCode::InvalidOraclePrice)
+        );
+    }
+
+    #[test]
+    fn round_publishes_only_when_every_asset_is_accumulated() {
+        let config = Pubkey::new_unique();
+        let mut round = ValuationRound::default();
+        round.begin(config, 2, 1_000).unwrap();
+        assert_eq!(round.round, 1);
+
+        round.accumulate(10, 1_010).unwrap();
+        assert_eq!(
+            round.finalize(2, 1_020).unwrap_err(),
+            error!(ErrorCode::ValuationIncomplete)
+        );
+        round.accumulate(5, 1_030).unwrap();
+        assert_eq!(
+            round.accumulate(5, 1_040).unwrap_err(),
+            error!(ErrorCode::ValuationRoundClosed)
+        );
+
+        // An asset registered mid-round invalidates the snapshot
+        assert_eq!(
+            round.finalize(3, 1_050).unwrap_err(),
+            error!(ErrorCode::ValuationIncomplete)
+        );
+        round.finalize(2, 1_050).unwrap();
+        assert_eq!(round.total_usd_value, 15);
+        assert_eq!(
+            round.finalize(2, 1_060).unwrap_err(),
+            error!(ErrorCode::ValuationRoundClosed)
+        );
+    }
+
+    #[test]
+    fn round_expires_after_its_time_bound() {
+        let mut round = ValuationRound::default();
+        round.begin(Pubkey::new_unique(), 1, 1_000).unwrap();
+        assert_eq!(
+            round
+                .begin(
+                    Pubkey::new_unique(),
+                    1,
+                    1_000 + MAX_VALUATION_ROUND_DURATION
+                )
+                .unwrap_err(),
+            error!(ErrorCode::ValuationRoundInProgress)
+        );
+
+        let expired = 1_000 + MAX_VALUATION_ROUND_DURATION + 1;
+        assert_eq!(
+            round.accumulate(10, expired).unwrap_err(),
+            error!(ErrorCode::ValuationRoundClosed)
+        );
+        round.begin(round.config, 1, expired).unwrap();
+        assert_eq!(round.round, 2);
+        assert_eq!(round.accumulated_count, 0);
+    }
+}
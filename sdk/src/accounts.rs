@@ -0,0 +1,190 @@
+//! Decoding of the program's accounts from raw account data, as returned by an RPC node.
+
+use anchor_lang::error::ErrorCode as AnchorErrorCode;
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use unitas_oracle::error::ErrorCode;
+
+use crate::{
+    AccountLabel, AssetLookupTable, Custodian, Operator, PriceUpdateV2, UnitasConfig, UsduConfig,
+    ValuationRound,
+};
+
+/// Decodes a config. Configs still on an older layout are rejected until migrated.
+pub fn decode_config(data: &[u8]) -> Result<UnitasConfig> {
+    require!(
+        data.len() >= UnitasConfig::LEN,
+        ErrorCode::UnknownAccountLayout
+    );
+    UnitasConfig::try_deserialize(&mut &data[..])
+}
+
+/// Decodes a zero-copy asset lookup table. RPC data carries no alignment guarantee, so the
+/// table is copied out rather than cast in place.
+pub fn decode_asset_lookup_table(data: &[u8]) -> Result<AssetLookupTable> {
+    require!(
+        data.starts_with(AssetLookupTable::DISCRIMINATOR),
+        AnchorErrorCode::AccountDiscriminatorMismatch
+    );
+    require!(
+        data.len() >= AssetLookupTable::LEN,
+        ErrorCode::UnknownAccountLayout
+    );
+    Ok(bytemuck::pod_read_unaligned(
+        &data[8..AssetLookupTable::LEN],
+    ))
+}
+
+pub fn decode_operator(data: &[u8]) -> Result<Operator> {
+    Operator::try_deserialize(&mut &data[..])
+}
+
+pub fn decode_custodian(data: &[u8]) -> Result<Custodian> {
+    Custodian::try_deserialize(&mut &data[..])
+}
+
+pub fn decode_account_label(data: &[u8]) -> Result<AccountLabel> {
+    AccountLabel::try_deserialize(&mut &data[..])
+}
+
+pub fn decode_valuation_round(data: &[u8]) -> Result<ValuationRound> {
+    ValuationRound::try_deserialize(&mut &data[..])
+}
+
+/// Decodes the USDU price feed, which uses the Pyth `PriceUpdateV2` layout.
+pub fn decode_price_feed(data: &[u8]) -> Result<PriceUpdateV2> {
+    PriceUpdateV2::try_deserialize(&mut &data[..])
+}
+
+/// Decodes the leading fields of the USDU program's config account.
+pub fn decode_usdu_config(data: &[u8]) -> Result<UsduConfig> {
+    let mut body = data.get(8..).ok_or(ErrorCode::InvalidUsduConfig)?;
+    UsduConfig::deserialize(&mut body).map_err(|_| error!(ErrorCode::InvalidUsduConfig))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ReservesUri;
+    use anchor_lang::solana_program::hash::hash;
+    use std::mem::{offset_of, size_of};
+
+    fn discriminator(account: &str) -> Vec<u8> {
+        hash(format!("account:{account}").as_bytes()).to_bytes()[..8].to_vec()
+    }
+
+    #[test]
+    fn discriminators_follow_account_names() {
+        assert_eq!(UnitasConfig::DISCRIMINATOR, discriminator("UnitasConfig"));
+        assert_eq!(
+            AssetLookupTable::DISCRIMINATOR,
+            discriminator("AssetLookupTable")
+        );
+        assert_eq!(Operator::DISCRIMINATOR, discriminator("Operator"));
+        assert_eq!(Custodian::DISCRIMINATOR, discriminator("Custodian"));
+        assert_eq!(AccountLabel::DISCRIMINATOR, discriminator("AccountLabel"));
+        assert_eq!(
+            ValuationRound::DISCRIMINATOR,
+            discriminator("ValuationRound")
+        );
+        // Shared with Pyth so the USDU feed decodes with Pyth tooling
+        assert_eq!(PriceUpdateV2::DISCRIMINATOR, discriminator("PriceUpdateV2"));
+    }
+
+    #[test]
+    fn account_sizes_are_pinned() {
+        assert_eq!(UnitasConfig::LEN, 621);
+        assert_eq!(AssetLookupTable::LEN, 880);
+        assert_eq!(Operator::LEN, 40);
+        assert_eq!(PriceUpdateV2::LEN, 134);
+        assert_eq!(ValuationRound::LEN, 81);
+        assert_eq!(AccountLabel::LEN, 105);
+    }
+
+    #[test]
+    fn asset_lookup_table_offsets_are_pinned() {
+        assert_eq!(offset_of!(AssetLookupTable, config), 0);
+        assert_eq!(offset_of!(AssetLookupTable, asset_mint), 32);
+        assert_eq!(offset_of!(AssetLookupTable, oracle_account), 64);
+        assert_eq!(offset_of!(AssetLookupTable, token_account_owners), 96);
+        assert_eq!(offset_of!(AssetLookupTable, token_account_owners_len), 608);
+        assert_eq!(offset_of!(AssetLookupTable, decimals), 612);
+        assert_eq!(offset_of!(AssetLookupTable, version), 613);
+        assert_eq!(offset_of!(AssetLookupTable, last_price), 616);
+        assert_eq!(offset_of!(AssetLookupTable, last_total_amount), 624);
+        assert_eq!(offset_of!(AssetLookupTable, last_usd_value), 632);
+        assert_eq!(offset_of!(AssetLookupTable, last_valued_at), 640);
+        assert_eq!(offset_of!(AssetLookupTable, last_price_exponent), 648);
+        assert_eq!(offset_of!(AssetLookupTable, last_accumulated_round), 656);
+        assert_eq!(offset_of!(AssetLookupTable, asset_kind), 664);
+        assert_eq!(offset_of!(AssetLookupTable, stake_pool), 672);
+        assert_eq!(offset_of!(AssetLookupTable, price_legs), 704);
+        assert_eq!(offset_of!(AssetLookupTable, price_legs_len), 768);
+        assert_eq!(offset_of!(AssetLookupTable, price_leg_divide_mask), 769);
+        assert_eq!(offset_of!(AssetLookupTable, peg_band_bps), 776);
+        assert_eq!(offset_of!(AssetLookupTable, is_depegged), 778);
+        assert_eq!(offset_of!(AssetLookupTable, pricing_mode), 779);
+        assert_eq!(offset_of!(AssetLookupTable, price_feed_ids_hash), 784);
+        assert_eq!(offset_of!(AssetLookupTable, reserved), 816);
+        assert_eq!(size_of::<AssetLookupTable>(), 872);
+    }
+
+    #[test]
+    fn decodes_config_at_full_length() {
+        let mut data = Vec::with_capacity(UnitasConfig::LEN);
+        UnitasConfig {
+            version: 1,
+            fund_id: 3,
+            nav: 1_000_000,
+            reserves_uri: ReservesUri::new("ipfs://reserves").unwrap(),
+            ..Default::default()
+        }
+        .try_serialize(&mut data)
+        .unwrap();
+        // Every field has a fixed size, so the serialized config fills the account exactly
+        assert_eq!(data.len(), UnitasConfig::LEN);
+
+        let config = decode_config(&data).unwrap();
+        assert_eq!(config.fund_id, 3);
+        assert_eq!(config.nav, 1_000_000);
+        assert_eq!(config.reserves_uri.as_str(), "ipfs://reserves");
+
+        assert_eq!(
+            decode_config(&data[..UnitasConfig::LEN - 1]).unwrap_err(),
+            error!(ErrorCode::UnknownAccountLayout)
+        );
+    }
+
+    #[test]
+    fn decodes_unaligned_asset_lookup_table() {
+        let mut table: AssetLookupTable = bytemuck::Zeroable::zeroed();
+        table.asset_mint = Pubkey::new_unique();
+        table.token_account_owners[0] = Pubkey::new_unique();
+        table.token_account_owners_len = 1;
+        table.decimals = 6;
+        table.version = 1;
+
+        // Offset the buffer by one byte so the table can't be cast in place
+        let mut buffer = vec![0u8];
+        buffer.extend_from_slice(AssetLookupTable::DISCRIMINATOR);
+        buffer.extend_from_slice(bytemuck::bytes_of(&table));
+        let data = &buffer[1..];
+
+        let decoded = decode_asset_lookup_table(data).unwrap();
+        assert_eq!(decoded.asset_mint, table.asset_mint);
+        assert_eq!(decoded.token_account_owners, table.token_account_owners);
+        assert_eq!(decoded.token_account_owners_len, 1);
+        assert_eq!(decoded.decimals, 6);
+        assert_eq!(decoded.version, 1);
+    }
+
+    #[test]
+    fn rejects_asset_lookup_table_with_wrong_discriminator() {
+        let mut data = UnitasConfig::DISCRIMINATOR.to_vec();
+        data.resize(AssetLookupTable::LEN, 0);
+        assert_eq!(
+            decode_asset_lookup_table(&data).unwrap_err(),
+            error!(AnchorErrorCode::AccountDiscriminatorMismatch)
+        );
+    }
+}
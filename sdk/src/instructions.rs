@@ -0,0 +1,700 @@
+//! Builders for every program instruction. PDAs are derived from the config, or the fund id
+//! where operators or asset lookup tables are involved, and the instruction arguments, so
+//! callers only pass the keys the program can't derive.
+
+use anchor_lang::prelude::{Pubkey, Result};
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::sysvar;
+use anchor_lang::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use unitas_oracle::{accounts, instruction};
+
+use crate::pda::{
+    account_label_address, asset_lookup_table_address, config_address, custodian_address,
+    event_authority_address, operator_address, price_feed_address, valuation_round_address,
+};
+use crate::{
+    AccountCategory, AssetLookupTable, CommitLiabilitiesArgs, CreateAssetLookupTableArgs,
+    CustodianAttestationArgs, PriceLeg, PricingMode, RecordAssetValuationArgs, UpdateAumUsdArgs,
+    ID,
+};
+
+fn build(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn price_feed(config: &Pubkey, publish: bool) -> Option<Pubkey> {
+    publish.then(|| price_feed_address(config))
+}
+
+/// Funds other than 0 can only be opened by fund 0's admin, so `admin` must be that admin.
+pub fn init_admin_config(admin: Pubkey, fund_id: u16, usdu_config: Pubkey) -> Instruction {
+    build(
+        accounts::InitAdminConfig {
+            admin,
+            config: config_address(fund_id),
+            fund_zero_config: (fund_id != 0).then(|| config_address(0)),
+            system_program: system_program::ID,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::InitAdminConfig {
+            fund_id,
+            usdu_config,
+        },
+    )
+}
+
+pub fn propose_new_admin(
+    current_admin: Pubkey,
+    config: Pubkey,
+    proposed_admin: Pubkey,
+) -> Instruction {
+    build(
+        accounts::ProposeNewAdmin {
+            current_admin,
+            proposed_admin,
+            config,
+            system_program: system_program::ID,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::ProposeNewAdmin {},
+    )
+}
+
+pub fn accept_admin_transfer(new_admin: Pubkey, config: Pubkey) -> Instruction {
+    build(
+        accounts::AcceptAdminTransfer {
+            new_admin,
+            config,
+            system_program: system_program::ID,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::AcceptAdminTransfer {},
+    )
+}
+
+pub fn add_operator(admin: Pubkey, fund_id: u16, user: Pubkey) -> Instruction {
+    let config = config_address(fund_id);
+    build(
+        accounts::AddOperator {
+            admin,
+            config,
+            operator: operator_address(fund_id, &user),
+            system_program: system_program::ID,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::AddOperator { user },
+    )
+}
+
+pub fn remove_operator(admin: Pubkey, fund_id: u16, user: Pubkey) -> Instruction {
+    let config = config_address(fund_id);
+    build(
+        accounts::RemoveOperator {
+            admin,
+            config,
+            operator: operator_address(fund_id, &user),
+            system_program: system_program::ID,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::RemoveOperator { user },
+    )
+}
+
+fn update_config(admin: Pubkey, config: Pubkey) -> accounts::UpdateConfig {
+    accounts::UpdateConfig {
+        admin,
+        config,
+        event_authority: event_authority_address(),
+        program: ID,
+    }
+}
+
+pub fn set_paused(admin: Pubkey, config: Pubkey, paused: bool) -> Instruction {
+    build(
+        update_config(admin, config),
+        instruction::SetPaused { paused },
+    )
+}
+
+pub fn set_max_aum_age(admin: Pubkey, config: Pubkey, max_aum_age: i64) -> Instruction {
+    build(
+        update_config(admin, config),
+        instruction::SetMaxAumAge { max_aum_age },
+    )
+}
+
+/// A zero `nav_peg_band_bps` stops reporting NAV peg deviations.
+pub fn set_nav_peg_band(admin: Pubkey, config: Pubkey, nav_peg_band_bps: u32) -> Instruction {
+    build(
+        update_config(admin, config),
+        instruction::SetNavPegBand { nav_peg_band_bps },
+    )
+}
+
+pub fn init_price_feed(admin: Pubkey, config: Pubkey, feed_id: [u8; 32]) -> Instruction {
+    build(
+        accounts::InitPriceFeed {
+            admin,
+            config,
+            price_feed: price_feed_address(&config),
+            system_program: system_program::ID,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::InitPriceFeed { feed_id },
+    )
+}
+
+pub fn create_asset_lookup_table(
+    admin: Pubkey,
+    fund_id: u16,
+    asset_mint: Pubkey,
+    args: CreateAssetLookupTableArgs,
+) -> Instruction {
+    let config = config_address(fund_id);
+    build(
+        accounts::CreateAssetLookupTable {
+            admin,
+            asset_lookup_table: asset_lookup_table_address(fund_id, &asset_mint),
+            asset_mint,
+            config,
+            system_program: system_program::ID,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::CreateAssetLookupTable { args },
+    )
+}
+
+pub fn close_asset_lookup_table(admin: Pubkey, fund_id: u16, asset_mint: Pubkey) -> Instruction {
+    build(
+        accounts::CloseAssetLookupTable {
+            admin,
+            config: config_address(fund_id),
+            asset_lookup_table: asset_lookup_table_address(fund_id, &asset_mint),
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::CloseAssetLookupTable {},
+    )
+}
+
+pub fn set_asset_price_path(
+    admin: Pubkey,
+    fund_id: u16,
+    asset_mint: Pubkey,
+    legs: Vec<PriceLeg>,
+    feed_ids: Vec<[u8; 32]>,
+) -> Instruction {
+    let config = config_address(fund_id);
+    build(
+        accounts::SetAssetPricePath {
+            admin,
+            config,
+            asset_lookup_table: asset_lookup_table_address(fund_id, &asset_mint),
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::SetAssetPricePath { legs, feed_ids },
+    )
+}
+
+pub fn set_asset_pricing_mode(
+    admin: Pubkey,
+    fund_id: u16,
+    asset_mint: Pubkey,
+    pricing_mode: PricingMode,
+) -> Instruction {
+    let config = config_address(fund_id);
+    build(
+        accounts::SetAssetPricingMode {
+            admin,
+            config,
+            asset_lookup_table: asset_lookup_table_address(fund_id, &asset_mint),
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::SetAssetPricingMode { pricing_mode },
+    )
+}
+
+/// A zero `peg_band_bps` values the table at its price rather than at $1.
+pub fn set_asset_peg_band(
+    admin: Pubkey,
+    fund_id: u16,
+    asset_mint: Pubkey,
+    peg_band_bps: u16,
+) -> Instruction {
+    let config = config_address(fund_id);
+    build(
+        accounts::SetAssetPegBand {
+            admin,
+            config,
+            asset_lookup_table: asset_lookup_table_address(fund_id, &asset_mint),
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::SetAssetPegBand { peg_band_bps },
+    )
+}
+
+/// Passing no `stake_pool` prices the table with its oracle alone.
+pub fn set_asset_stake_pool(
+    admin: Pubkey,
+    fund_id: u16,
+    asset_mint: Pubkey,
+    stake_pool: Option<Pubkey>,
+) -> Instruction {
+    let config = config_address(fund_id);
+    build(
+        accounts::SetAssetStakePool {
+            admin,
+            config,
+            asset_lookup_table: asset_lookup_table_address(fund_id, &asset_mint),
+            stake_pool,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::SetAssetStakePool {},
+    )
+}
+
+pub fn migrate_config(admin: Pubkey, config: Pubkey) -> Instruction {
+    build(
+        accounts::MigrateConfig {
+            admin,
+            config,
+            system_program: system_program::ID,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::MigrateConfig {},
+    )
+}
+
+pub fn migrate_asset_lookup_table(admin: Pubkey, fund_id: u16, asset_mint: Pubkey) -> Instruction {
+    let config = config_address(fund_id);
+    build(
+        accounts::MigrateAssetLookupTable {
+            admin,
+            config,
+            asset_lookup_table: asset_lookup_table_address(fund_id, &asset_mint),
+            system_program: system_program::ID,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::MigrateAssetLookupTable {},
+    )
+}
+
+pub fn add_custodian(admin: Pubkey, config: Pubkey, eth_address: [u8; 20]) -> Instruction {
+    build(
+        accounts::AddCustodian {
+            admin,
+            config,
+            custodian: custodian_address(&config, &eth_address),
+            system_program: system_program::ID,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::AddCustodian { eth_address },
+    )
+}
+
+/// `publish_price_feed` republishes the NAV to the USDU price feed. It must be set once the feed
+/// has been created with `init_price_feed`, and unset before; the same holds for every builder
+/// taking it.
+pub fn remove_custodian(
+    admin: Pubkey,
+    config: Pubkey,
+    eth_address: [u8; 20],
+    publish_price_feed: bool,
+) -> Instruction {
+    build(
+        accounts::RemoveCustodian {
+            admin,
+            config,
+            custodian: custodian_address(&config, &eth_address),
+            price_feed: price_feed(&config, publish_price_feed),
+            system_program: system_program::ID,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::RemoveCustodian { eth_address },
+    )
+}
+
+pub fn add_account(user: Pubkey, fund_id: u16, asset_mint: Pubkey, account: Pubkey) -> Instruction {
+    let config = config_address(fund_id);
+    build(
+        accounts::AddAccount {
+            user,
+            asset_lookup_table: asset_lookup_table_address(fund_id, &asset_mint),
+            asset_mint,
+            config,
+            operator: operator_address(fund_id, &user),
+            system_program: system_program::ID,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::AddAccount { account },
+    )
+}
+
+pub fn remove_account(
+    user: Pubkey,
+    fund_id: u16,
+    asset_mint: Pubkey,
+    account: Pubkey,
+) -> Instruction {
+    let config = config_address(fund_id);
+    build(
+        accounts::RemoveAccount {
+            user,
+            asset_lookup_table: asset_lookup_table_address(fund_id, &asset_mint),
+            asset_mint,
+            config,
+            operator: operator_address(fund_id, &user),
+            system_program: system_program::ID,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::RemoveAccount { account },
+    )
+}
+
+pub fn add_accounts(
+    user: Pubkey,
+    fund_id: u16,
+    asset_mint: Pubkey,
+    accounts: Vec<Pubkey>,
+) -> Instruction {
+    let config = config_address(fund_id);
+    build(
+        accounts::AddAccounts {
+            user,
+            asset_lookup_table: asset_lookup_table_address(fund_id, &asset_mint),
+            asset_mint,
+            config,
+            operator: operator_address(fund_id, &user),
+            system_program: system_program::ID,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::AddAccounts { accounts },
+    )
+}
+
+pub fn remove_accounts(
+    user: Pubkey,
+    fund_id: u16,
+    asset_mint: Pubkey,
+    accounts: Vec<Pubkey>,
+) -> Instruction {
+    let config = config_address(fund_id);
+    build(
+        accounts::RemoveAccounts {
+            user,
+            asset_lookup_table: asset_lookup_table_address(fund_id, &asset_mint),
+            asset_mint,
+            config,
+            operator: operator_address(fund_id, &user),
+            system_program: system_program::ID,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::RemoveAccounts { accounts },
+    )
+}
+
+pub fn set_account_label(
+    user: Pubkey,
+    fund_id: u16,
+    account: Pubkey,
+    label: String,
+    category: AccountCategory,
+) -> Instruction {
+    let config = config_address(fund_id);
+    build(
+        accounts::SetAccountLabel {
+            user,
+            config,
+            operator: operator_address(fund_id, &user),
+            account_label: account_label_address(&config, &account),
+            system_program: system_program::ID,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::SetAccountLabel {
+            account,
+            label,
+            category,
+        },
+    )
+}
+
+pub fn record_asset_valuation(
+    user: Pubkey,
+    fund_id: u16,
+    asset_mint: Pubkey,
+    args: RecordAssetValuationArgs,
+) -> Instruction {
+    let config = config_address(fund_id);
+    build(
+        accounts::RecordAssetValuation {
+            user,
+            asset_lookup_table: asset_lookup_table_address(fund_id, &asset_mint),
+            config,
+            operator: operator_address(fund_id, &user),
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::RecordAssetValuation { args },
+    )
+}
+
+pub fn begin_valuation(user: Pubkey, fund_id: u16) -> Instruction {
+    let config = config_address(fund_id);
+    build(
+        accounts::BeginValuation {
+            user,
+            config,
+            operator: operator_address(fund_id, &user),
+            valuation_round: valuation_round_address(&config),
+            system_program: system_program::ID,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::BeginValuation {},
+    )
+}
+
+/// Values `asset_lookup_table` into the open round, passing the rest of its price path and
+/// the holding account of each of its owners as remaining accounts. Fails only for a table
+/// of an unknown asset kind.
+pub fn accumulate_asset(
+    fund_id: u16,
+    asset_lookup_table: &AssetLookupTable,
+) -> Result<Instruction> {
+    let config = config_address(fund_id);
+    let mut ix = build(
+        accounts::AccumulateAsset {
+            config,
+            valuation_round: valuation_round_address(&config),
+            asset_lookup_table: asset_lookup_table_address(fund_id, &asset_lookup_table.asset_mint),
+            oracle: asset_lookup_table.oracle_account,
+            stake_pool: (asset_lookup_table.stake_pool != Pubkey::default())
+                .then_some(asset_lookup_table.stake_pool),
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::AccumulateAsset {},
+    );
+    for leg in &asset_lookup_table.price_path()[1..] {
+        ix.accounts
+            .push(AccountMeta::new_readonly(leg.oracle, false));
+    }
+    let owners = &asset_lookup_table.token_account_owners
+        [..asset_lookup_table.token_account_owners_len as usize];
+    for owner in owners {
+        let holding = asset_lookup_table.holding_address(owner)?;
+        ix.accounts.push(AccountMeta::new_readonly(holding, false));
+    }
+    Ok(ix)
+}
+
+pub fn finalize_valuation(
+    config: Pubkey,
+    usdu_config: Pubkey,
+    publish_price_feed: bool,
+) -> Instruction {
+    build(
+        accounts::FinalizeValuation {
+            config,
+            valuation_round: valuation_round_address(&config),
+            usdu_config,
+            price_feed: price_feed(&config, publish_price_feed),
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::FinalizeValuation {},
+    )
+}
+
+/// `usdu_config` must be the USDU config account stored in `UnitasConfig::usdu_config`.
+pub fn update_aum_usd(
+    user: Pubkey,
+    fund_id: u16,
+    usdu_config: Pubkey,
+    publish_price_feed: bool,
+    args: UpdateAumUsdArgs,
+) -> Instruction {
+    let config = config_address(fund_id);
+    build(
+        accounts::UpdateAumUsd {
+            user,
+            config,
+            operator: operator_address(fund_id, &user),
+            usdu_config,
+            price_feed: price_feed(&config, publish_price_feed),
+            system_program: system_program::ID,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::UpdateAumUsd { args },
+    )
+}
+
+pub fn get_nav(config: Pubkey) -> Instruction {
+    build(accounts::GetNav { config }, instruction::GetNav {})
+}
+
+pub fn assert_nav_fresh(config: Pubkey, max_age: i64) -> Instruction {
+    build(
+        accounts::AssertNav { config },
+        instruction::AssertNavFresh { max_age },
+    )
+}
+
+pub fn assert_collateral_ratio(config: Pubkey, min_bps: u64) -> Instruction {
+    build(
+        accounts::AssertNav { config },
+        instruction::AssertCollateralRatio { min_bps },
+    )
+}
+
+pub fn commit_liabilities(user: Pubkey, fund_id: u16, args: CommitLiabilitiesArgs) -> Instruction {
+    let config = config_address(fund_id);
+    build(
+        accounts::CommitLiabilities {
+            user,
+            config,
+            operator: operator_address(fund_id, &user),
+            system_program: system_program::ID,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::CommitLiabilities { args },
+    )
+}
+
+/// Must directly follow the secp256k1 instruction carrying the custodian's signature over
+/// `Custodian::attestation_message`.
+pub fn submit_custodian_attestation(
+    config: Pubkey,
+    eth_address: [u8; 20],
+    publish_price_feed: bool,
+    args: CustodianAttestationArgs,
+) -> Instruction {
+    build(
+        accounts::SubmitCustodianAttestation {
+            config,
+            custodian: custodian_address(&config, &eth_address),
+            price_feed: price_feed(&config, publish_price_feed),
+            instructions_sysvar: sysvar::instructions::ID,
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::SubmitCustodianAttestation { args },
+    )
+}
+
+pub fn expire_custodian_attestation(
+    config: Pubkey,
+    eth_address: [u8; 20],
+    publish_price_feed: bool,
+) -> Instruction {
+    build(
+        accounts::ExpireCustodianAttestation {
+            config,
+            custodian: custodian_address(&config, &eth_address),
+            price_feed: price_feed(&config, publish_price_feed),
+            event_authority: event_authority_address(),
+            program: ID,
+        },
+        instruction::ExpireCustodianAttestation {},
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::Discriminator;
+
+    #[test]
+    fn add_account_derives_pdas_and_flags() {
+        let user = Pubkey::new_unique();
+        let config = config_address(0);
+        let asset_mint = Pubkey::new_unique();
+        let account = Pubkey::new_unique();
+        let ix = add_account(user, 0, asset_mint, account);
+
+        assert_eq!(ix.program_id, ID);
+        let keys: Vec<Pubkey> = ix.accounts.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(
+            keys,
+            vec![
+                user,
+                asset_lookup_table_address(0, &asset_mint),
+                asset_mint,
+                config,
+                operator_address(0, &user),
+                system_program::ID,
+                event_authority_address(),
+                ID,
+            ]
+        );
+        assert!(ix.accounts[0].is_signer);
+        assert!(ix.accounts[1].is_writable);
+
+        assert!(ix.data.starts_with(instruction::AddAccount::DISCRIMINATOR));
+        assert_eq!(&ix.data[8..], account.as_ref());
+    }
+
+    #[test]
+    fn fund_zero_keeps_the_legacy_addresses() {
+        use crate::{ADMIN_CONFIG_SEED, ASSET_LOOKUP_TABLE_SEED, OPERATOR_SEED};
+
+        let legacy = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &ID).0;
+        let user = Pubkey::new_unique();
+        let asset_mint = Pubkey::new_unique();
+        assert_eq!(config_address(0), legacy(&[ADMIN_CONFIG_SEED.as_bytes()]));
+        assert_eq!(
+            operator_address(0, &user),
+            legacy(&[OPERATOR_SEED.as_bytes(), user.as_ref()])
+        );
+        assert_eq!(
+            asset_lookup_table_address(0, &asset_mint),
+            legacy(&[ASSET_LOOKUP_TABLE_SEED.as_bytes(), asset_mint.as_ref()])
+        );
+        assert_ne!(operator_address(1, &user), operator_address(0, &user));
+    }
+
+    #[test]
+    fn init_admin_config_targets_the_fund_config() {
+        let admin = Pubkey::new_unique();
+        let usdu_config = Pubkey::new_unique();
+        let ix = init_admin_config(admin, 7, usdu_config);
+
+        assert_eq!(ix.accounts[1].pubkey, config_address(7));
+        assert_ne!(config_address(7), config_address(0));
+        // Fund 0's admin authorises the new fund
+        assert_eq!(ix.accounts[2].pubkey, config_address(0));
+        assert_eq!(
+            init_admin_config(admin, 0, usdu_config).accounts[2].pubkey,
+            ID
+        );
+        assert_eq!(&ix.data[8..10], 7u16.to_le_bytes().as_ref());
+        assert_eq!(&ix.data[10..], usdu_config.as_ref());
+    }
+}
@@ -0,0 +1,25 @@
+//! Off-chain companion to the `unitas-oracle` program: account decoding, PDA derivation and
+//! instruction builders, all backed by the program's own types so they can't drift from it.
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+
+pub use unitas_oracle::event;
+pub use unitas_oracle::instructions::{
+    CommitLiabilitiesArgs, CreateAssetLookupTableArgs, CustodianAttestationArgs, NavData,
+    RecordAssetValuationArgs, UpdateAumUsdArgs,
+};
+pub use unitas_oracle::state::{
+    compose_price, fund_seed, usd_value, AccountCategory, AccountLabel, AssetKind,
+    AssetLookupTable, CollateralThresholdCrossing, Custodian, NavPegCheck, NavPegDirection,
+    NavRefresh, NavStatus, Operator, PriceFeedMessage, PriceLeg, PriceUpdateV2, PricingMode,
+    ReservesUri, StakePoolRate, UnitasConfig, UsduConfig, ValuationRound, ValuationSource,
+    VerificationLevel, MAX_ACCOUNTS_PER_ASSET, MAX_ACCOUNT_LABEL_LEN, MAX_PRICE_LEGS,
+};
+pub use unitas_oracle::{
+    ACCOUNT_LABEL_SEED, ADMIN_CONFIG_SEED, ASSET_LOOKUP_TABLE_SEED, ASSET_LOOKUP_TABLE_VERSION,
+    COLLATERAL_RATIO_THRESHOLDS_BPS, CONFIG_VERSION, CUSTODIAN_SEED, ID, MAX_ORACLE_PRICE_AGE,
+    NATIVE_SOL_ASSET_MINT, NAV_DECIMALS, OPERATOR_SEED, SOL_DECIMALS, SPL_STAKE_POOL_PROGRAM_ID,
+    STAKE_ASSET_MINT, USDU_PRICE_FEED_SEED, VALUATION_ROUND_SEED,
+};
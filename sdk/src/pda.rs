@@ -0,0 +1,78 @@
+//! Addresses of the program's accounts. Each mirrors the seeds of the matching `#[account]`
+//! constraint in the program.
+
+use anchor_lang::prelude::Pubkey;
+
+use crate::{
+    fund_seed, ACCOUNT_LABEL_SEED, ADMIN_CONFIG_SEED, ASSET_LOOKUP_TABLE_SEED, CUSTODIAN_SEED, ID,
+    OPERATOR_SEED, USDU_PRICE_FEED_SEED, VALUATION_ROUND_SEED,
+};
+
+pub fn config_address(fund_id: u16) -> Pubkey {
+    Pubkey::find_program_address(
+        &[ADMIN_CONFIG_SEED.as_bytes(), fund_seed(fund_id).as_ref()],
+        &ID,
+    )
+    .0
+}
+
+pub fn operator_address(fund_id: u16, user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            OPERATOR_SEED.as_bytes(),
+            fund_seed(fund_id).as_ref(),
+            user.as_ref(),
+        ],
+        &ID,
+    )
+    .0
+}
+
+pub fn asset_lookup_table_address(fund_id: u16, asset_mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            ASSET_LOOKUP_TABLE_SEED.as_bytes(),
+            fund_seed(fund_id).as_ref(),
+            asset_mint.as_ref(),
+        ],
+        &ID,
+    )
+    .0
+}
+
+pub fn custodian_address(config: &Pubkey, eth_address: &[u8; 20]) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            CUSTODIAN_SEED.as_bytes(),
+            config.as_ref(),
+            eth_address.as_ref(),
+        ],
+        &ID,
+    )
+    .0
+}
+
+pub fn account_label_address(config: &Pubkey, account: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[
+            ACCOUNT_LABEL_SEED.as_bytes(),
+            config.as_ref(),
+            account.as_ref(),
+        ],
+        &ID,
+    )
+    .0
+}
+
+pub fn price_feed_address(config: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[USDU_PRICE_FEED_SEED.as_bytes(), config.as_ref()], &ID).0
+}
+
+pub fn valuation_round_address(config: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[VALUATION_ROUND_SEED.as_bytes(), config.as_ref()], &ID).0
+}
+
+/// Signer of the program's self-CPI events, required by every instruction that emits one.
+pub fn event_authority_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"__event_authority"], &ID).0
+}